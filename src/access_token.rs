@@ -8,11 +8,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/access_tokens",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/access_tokens",
     cli_help = "Get a list of project access tokens.",
   ),
   endpoint(
     route = "/projects/{id}/access_tokens/{token_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/access_tokens/{token_id}",
     cli_help = "Get a project access token by ID.",
   ),
@@ -21,6 +23,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "AccessTokenCreatePayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/access_tokens/create",
     cli_help = "Create a project access token.",
   ),
@@ -38,11 +41,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/access_tokens",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/access_tokens",
     cli_help = "Get a list of group access tokens.",
   ),
   endpoint(
     route = "/groups/{id}/access_tokens/{token_id}",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/access_tokens/{token_id}",
     cli_help = "Get a group access token by ID.",
   ),
@@ -51,6 +56,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "AccessTokenCreatePayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/access_tokens/create",
     cli_help = "Create a group access token.",
   ),
@@ -100,3 +106,144 @@ pub(crate) struct AccessTokenCreatePayload {
   #[serde(skip_serializing_if = "Option::is_none")]
   expires_at: Option<String>,
 }
+
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/deploy_tokens",
+    query_struct = "DeployTokenFilter",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/deploy_tokens",
+    cli_help = "Get a list of a project's deploy tokens.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_tokens/{token_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deploy_tokens/{token_id}",
+    cli_help = "Get a single project's deploy token by ID.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_tokens",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "DeployTokenCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deploy_tokens/create",
+    cli_help = "Create a project deploy token.",
+    cli_long_help = "Create a project deploy token. Token values are returned once so, make sure you save it as you can't access it again.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_tokens/{token_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    cli_no_output,
+    result_struct = "EmptyResponse",
+    cli_route = "/projects/{id}/deploy_tokens/{token_id}/revoke",
+    cli_help = "Revoke a project deploy token.",
+  )
+)]
+#[api(
+  endpoint(
+    route = "/groups/{id}/deploy_tokens",
+    query_struct = "DeployTokenFilter",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/groups/{id}/deploy_tokens",
+    cli_help = "Get a list of a group's deploy tokens.",
+  ),
+  endpoint(
+    route = "/groups/{id}/deploy_tokens/{token_id}",
+    extra_action = "output_result",
+    cli_route = "/groups/{id}/deploy_tokens/{token_id}",
+    cli_help = "Get a single group's deploy token by ID.",
+  ),
+  endpoint(
+    route = "/groups/{id}/deploy_tokens",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "DeployTokenCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/groups/{id}/deploy_tokens/create",
+    cli_help = "Create a group deploy token.",
+    cli_long_help = "Create a group deploy token. Token values are returned once so, make sure you save it as you can't access it again.",
+  ),
+  endpoint(
+    route = "/groups/{id}/deploy_tokens/{token_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    cli_no_output,
+    result_struct = "EmptyResponse",
+    cli_route = "/groups/{id}/deploy_tokens/{token_id}/revoke",
+    cli_help = "Revoke a group deploy token.",
+  )
+)]
+pub(crate) struct DeployToken {
+  id: u32,
+  name: String,
+  username: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  expires_at: Option<String>,
+  scopes: Vec<String>,
+  revoked: bool,
+  expired: bool,
+  /// Only given by the creation of the token.
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  token: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct DeployTokenFilter {
+  #[api(no_short, heading = "Filters", help = "Limit by active status.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  active: Option<bool>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct DeployTokenCreatePayload {
+  #[api(no_short, help = "New deploy token's name")]
+  name: String,
+  #[api(
+    no_short,
+    help = "Deploy token scopes",
+    possible_values = "read_repository,read_registry,write_registry,read_package_registry,write_package_registry"
+  )]
+  scopes: Vec<String>,
+  #[api(
+    no_short,
+    long = "expires-at",
+    help = "Expiration date for the deploy token.",
+    long_help = "Expiration date for the deploy token. Does not expire if no value is provided. Expected in ISO 8601 format (`2019-03-15T08:00:00Z`)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  expires_at: Option<String>,
+  #[api(
+    no_short,
+    help = "Username for deploy token.",
+    long_help = "Username for deploy token. Default is `gitlab+deploy-token-<id>`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  username: Option<String>,
+}
@@ -0,0 +1,559 @@
+use crate::user::User;
+use crud_api::{Api, ApiInput};
+use crud_pretty_struct::{formatters::bool_check_formatter, PrettyPrint};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approvals",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/approvals",
+    cli_help = "Gets the approvals of a merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approve",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "ApprovePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/approve",
+    cli_help = "Approves a merge request.",
+    cli_long_help = "Approves a merge request. If you did not set an approval password, the `sha` option is the only way to ensure the merge request was not updated since you reviewed it."
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/unapprove",
+    method = "POST",
+    result_ok_status = "CREATED",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/merge_requests/{iid}/unapprove",
+    cli_help = "Removes your approval from a merge request.",
+  )
+)]
+#[derive(PrettyPrint)]
+pub(crate) struct MergeRequestApprovals {
+  #[pretty(formatter=bool_check_formatter)]
+  approved: bool,
+  approvals_required: u32,
+  approvals_left: u32,
+  #[api(table_skip)]
+  #[serde(default)]
+  approved_by: Vec<Approver>,
+  #[api(table_skip)]
+  #[serde(default)]
+  approval_rules_left: Vec<ApprovalRuleLeft>,
+  #[api(table_skip)]
+  #[serde(default)]
+  suggested_approvers: Vec<User>,
+  #[api(table_skip)]
+  #[pretty(formatter=bool_check_formatter)]
+  user_has_approved: bool,
+  #[api(table_skip)]
+  #[pretty(formatter=bool_check_formatter)]
+  user_can_approve: bool,
+}
+
+/// Summary of the [MergeRequestApprovals] of a merge request, joined to the lists of merge
+/// requests by `--approvals`: the approvers and the rules are given by their names.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct ApprovalSummary {
+  approved: bool,
+  approved_by: String,
+  approvals_left: u32,
+  rules_left: String,
+}
+
+impl From<&MergeRequestApprovals> for ApprovalSummary {
+  fn from(approvals: &MergeRequestApprovals) -> Self {
+    ApprovalSummary {
+      approved: approvals.approved,
+      approved_by: names(&approvals.approved_by),
+      approvals_left: approvals.approvals_left,
+      rules_left: names(&approvals.approval_rules_left),
+    }
+  }
+}
+
+fn names<T: Display>(items: &[T]) -> String {
+  items
+    .iter()
+    .map(ToString::to_string)
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct Approver {
+  user: User,
+}
+
+impl Display for Approver {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.user)
+  }
+}
+
+/// Approval rule still waiting for approvals.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct ApprovalRuleLeft {
+  id: u32,
+  name: String,
+  rule_type: String,
+}
+
+impl Display for ApprovalRuleLeft {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.name)
+  }
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/approvals",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/approvals",
+    cli_help = "Get the approval configuration of a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/approvals",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "ProjectApprovalsPayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/approvals/edit",
+    cli_help = "Change the approval configuration of a project.",
+  )
+)]
+#[derive(PrettyPrint)]
+pub(crate) struct ProjectApprovals {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none)]
+  approvals_before_merge: Option<u32>,
+  #[pretty(formatter=bool_check_formatter)]
+  reset_approvals_on_push: bool,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none, formatter=bool_check_formatter)]
+  selective_code_owner_removals: Option<bool>,
+  #[pretty(formatter=bool_check_formatter)]
+  disable_overriding_approvers_per_merge_request: bool,
+  #[pretty(formatter=bool_check_formatter)]
+  merge_requests_author_approval: bool,
+  #[pretty(formatter=bool_check_formatter)]
+  merge_requests_disable_committers_approval: bool,
+  #[pretty(formatter=bool_check_formatter)]
+  require_password_to_approve: bool,
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(endpoint(
+  route = "/projects/{id}/merge_requests/{iid}/approval_state",
+  extra_action = "output_result",
+  cli_route = "/projects/{id}/merge_requests/{iid}/approval_state",
+  cli_help = "Shows the approval rules of a merge request and whether they are satisfied.",
+  cli_long_help = "Shows the approval rules of a merge request and whether they are satisfied. `approved_by` lists the users who approved the rule and `eligible_approvers` the users who can still approve it."
+))]
+#[derive(PrettyPrint)]
+pub(crate) struct ApprovalState {
+  #[pretty(formatter=bool_check_formatter)]
+  approval_rules_overwritten: bool,
+  #[api(table_skip)]
+  #[pretty(is_pretty)]
+  rules: Vec<ApprovalRule>,
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/approval_rules",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "ApprovalRuleFilter",
+    cli_route = "/projects/{id}/approval_rules",
+    cli_help = "Get the approval rules of a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/approval_rules/{approval_rule_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/approval_rules/{approval_rule_id}",
+    cli_help = "Get a single approval rule of a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/approval_rules",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "ApprovalRuleCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/approval_rules/create",
+    cli_help = "Creates a project-level approval rule.",
+  ),
+  endpoint(
+    route = "/projects/{id}/approval_rules/{approval_rule_id}",
+    method = "PUT",
+    payload_struct = "ApprovalRuleUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/approval_rules/{approval_rule_id}/edit",
+    cli_help = "Updates a project-level approval rule.",
+  ),
+  endpoint(
+    route = "/projects/{id}/approval_rules/{approval_rule_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/approval_rules/{approval_rule_id}/delete",
+    cli_help = "Deletes a project-level approval rule.",
+  )
+)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approval_rules",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/merge_requests/{iid}/approval_rules",
+    cli_help = "Get the approval rules of a merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approval_rules/{approval_rule_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/approval_rules/{approval_rule_id}",
+    cli_help = "Get a single approval rule of a merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approval_rules",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "MergeRequestApprovalRuleCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/approval_rules/create",
+    cli_help = "Creates a merge request-level approval rule.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approval_rules/{approval_rule_id}",
+    method = "PUT",
+    payload_struct = "MergeRequestApprovalRuleUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/approval_rules/{approval_rule_id}/edit",
+    cli_help = "Updates a merge request-level approval rule.",
+    cli_long_help = "Updates a merge request-level approval rule. You can't update `report_approver` or `code_owner` rules, as these are system generated rules."
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/approval_rules/{approval_rule_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/merge_requests/{iid}/approval_rules/{approval_rule_id}/delete",
+    cli_help = "Deletes a merge request-level approval rule.",
+  )
+)]
+#[derive(PrettyPrint)]
+pub(crate) struct ApprovalRule {
+  id: u32,
+  name: String,
+  rule_type: String,
+  approvals_required: u32,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none, formatter=bool_check_formatter)]
+  approved: Option<bool>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none)]
+  approved_by: Option<Vec<User>>,
+  #[api(table_skip)]
+  #[serde(default)]
+  eligible_approvers: Vec<User>,
+  #[api(table_skip)]
+  #[serde(default)]
+  users: Vec<User>,
+  #[api(table_skip)]
+  #[serde(default)]
+  groups: Vec<ApprovalGroup>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  contains_hidden_groups: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct ApprovalGroup {
+  id: u32,
+  name: String,
+  full_path: String,
+}
+
+impl Display for ApprovalGroup {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.full_path)
+  }
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ApprovePayload {
+  #[api(
+    no_short,
+    help = "The HEAD of the merge request.",
+    long_help = "The HEAD of the merge request. If passed, it must match the current HEAD of the merge request."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sha: Option<String>,
+
+  #[api(
+    no_short,
+    long = "approval-password",
+    help = "Current user's password.",
+    long_help = "Current user's password. Required if `Require user re-authentication to approve` is enabled in the project settings."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  approval_password: Option<String>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct ApprovalRuleFilter {
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ApprovalRuleCreatePayload {
+  #[api(no_short, help = "The name of the approval rule.")]
+  name: String,
+
+  #[api(
+    no_short,
+    long = "approvals-required",
+    help = "The number of required approvals for this rule."
+  )]
+  approvals_required: u32,
+
+  #[api(
+    no_short,
+    long = "rule-type",
+    help = "The type of rule.",
+    long_help = "The type of rule. `any_approver` is a pre-configured default rule with `approvals_required` set to `0`. Other rules are `regular`.",
+    possible_values = "any_approver,regular"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  rule_type: Option<String>,
+
+  #[api(no_short, long = "user-ids", help = "The IDs of users as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_ids: Option<Vec<u32>>,
+
+  #[api(no_short, long = "usernames", help = "The usernames for this rule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  usernames: Option<Vec<String>>,
+
+  #[api(no_short, long = "group-ids", help = "The IDs of groups as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  group_ids: Option<Vec<u32>>,
+
+  #[api(
+    no_short,
+    long = "protected-branch-ids",
+    help = "The IDs of protected branches to scope the rule by."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  protected_branch_ids: Option<Vec<u32>>,
+
+  #[api(
+    no_short,
+    long = "applies-to-all-protected-branches",
+    help = "Whether the rule is applied to all protected branches.",
+    long_help = "Whether the rule is applied to all protected branches. If set to `true`, the value of `protected_branch_ids` is ignored."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  applies_to_all_protected_branches: Option<bool>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ApprovalRuleUpdatePayload {
+  #[api(no_short, help = "The name of the approval rule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    long = "approvals-required",
+    help = "The number of required approvals for this rule."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  approvals_required: Option<u32>,
+
+  #[api(no_short, long = "user-ids", help = "The IDs of users as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_ids: Option<Vec<u32>>,
+
+  #[api(no_short, long = "usernames", help = "The usernames for this rule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  usernames: Option<Vec<String>>,
+
+  #[api(no_short, long = "group-ids", help = "The IDs of groups as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  group_ids: Option<Vec<u32>>,
+
+  #[api(
+    no_short,
+    long = "protected-branch-ids",
+    help = "The IDs of protected branches to scope the rule by."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  protected_branch_ids: Option<Vec<u32>>,
+
+  #[api(
+    no_short,
+    long = "applies-to-all-protected-branches",
+    help = "Whether the rule is applied to all protected branches.",
+    long_help = "Whether the rule is applied to all protected branches. If set to `true`, the value of `protected_branch_ids` is ignored."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  applies_to_all_protected_branches: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "remove-hidden-groups",
+    help = "Whether hidden groups should be removed."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  remove_hidden_groups: Option<bool>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct MergeRequestApprovalRuleCreatePayload {
+  #[api(no_short, help = "The name of the approval rule.")]
+  name: String,
+
+  #[api(
+    no_short,
+    long = "approvals-required",
+    help = "The number of required approvals for this rule."
+  )]
+  approvals_required: u32,
+
+  #[api(
+    no_short,
+    long = "approval-project-rule-id",
+    help = "The ID of a project-level approval rule.",
+    long_help = "The ID of a project-level approval rule. The `name`, `approvals_required` and the approvers are copied from the project-level rule."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  approval_project_rule_id: Option<u32>,
+
+  #[api(no_short, long = "user-ids", help = "The IDs of users as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_ids: Option<Vec<u32>>,
+
+  #[api(no_short, long = "usernames", help = "The usernames for this rule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  usernames: Option<Vec<String>>,
+
+  #[api(no_short, long = "group-ids", help = "The IDs of groups as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  group_ids: Option<Vec<u32>>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct MergeRequestApprovalRuleUpdatePayload {
+  #[api(no_short, help = "The name of the approval rule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    long = "approvals-required",
+    help = "The number of required approvals for this rule."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  approvals_required: Option<u32>,
+
+  #[api(no_short, long = "user-ids", help = "The IDs of users as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_ids: Option<Vec<u32>>,
+
+  #[api(no_short, long = "usernames", help = "The usernames for this rule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  usernames: Option<Vec<String>>,
+
+  #[api(no_short, long = "group-ids", help = "The IDs of groups as approvers.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  group_ids: Option<Vec<u32>>,
+
+  #[api(
+    no_short,
+    long = "remove-hidden-groups",
+    help = "Whether hidden groups should be removed."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  remove_hidden_groups: Option<bool>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ProjectApprovalsPayload {
+  #[api(
+    no_short,
+    long = "reset-approvals-on-push",
+    help = "Reset approvals on a new push."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  reset_approvals_on_push: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "selective-code-owner-removals",
+    help = "Reset approvals from Code Owners if their files changed.",
+    long_help = "Reset approvals from Code Owners if their files changed. Can be enabled only if `reset_approvals_on_push` is disabled."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  selective_code_owner_removals: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "disable-overriding-approvers-per-merge-request",
+    help = "Allow or prevent overriding approvers per merge request."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  disable_overriding_approvers_per_merge_request: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "merge-requests-author-approval",
+    help = "Allow or prevent authors from self approving merge requests."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  merge_requests_author_approval: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "merge-requests-disable-committers-approval",
+    help = "Allow or prevent committers from self approving merge requests."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  merge_requests_disable_committers_approval: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "require-password-to-approve",
+    help = "Require approver to enter a password to authenticate before adding the approval."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  require_password_to_approve: Option<bool>,
+}
@@ -7,11 +7,13 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/badges",
     query_struct = "BadgeQuery",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/badges",
     cli_help = "Gets a list of a project's badges and its group badges.",
   ),
   endpoint(
     route = "/projects/{id}/badges/{badge_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/badges/{badge_id}",
     cli_help = "Gets a badge of a project.",
   ),
@@ -20,6 +22,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "BadgeCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/badges/create",
     cli_help = "Adds a badge to a project.",
   ),
@@ -27,6 +30,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/badges/{badge_id}",
     method = "PUT",
     payload_struct = "BadgeCreatePayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/badges/{badge_id}/edit",
     cli_help = "Updates a badge of a project.",
   ),
@@ -46,11 +50,13 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/badges",
     query_struct = "BadgeQuery",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/badges",
     cli_help = "Gets a list of a group's badges.",
   ),
   endpoint(
     route = "/groups/{id}/badges/{badge_id}",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/badges/{badge_id}",
     cli_help = "Gets a badge of a group.",
   ),
@@ -59,6 +65,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "BadgeCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/badges/create",
     cli_help = "Adds a badge to a group.",
   ),
@@ -66,6 +73,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/badges/{badge_id}",
     method = "PUT",
     payload_struct = "BadgeCreatePayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/badges/{badge_id}/edit",
     cli_help = "Updates a badge of a group.",
   ),
@@ -1,19 +1,35 @@
+use config::Config;
 use crud_api::{Api, ApiInput};
+use miette::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::commit::Commit;
+use crate::{
+  commit::Commit,
+  output::{output_joined_rows, output_rows},
+  pagination,
+  protected::{ProtectedAccessLevel, ProtectedBranch},
+  session::Session,
+};
 
-#[derive(Api, Serialize, Deserialize, Debug, Default)]
+/// Default columns of the branches joined with their protection by `--protected`.
+const PROTECTED_COLUMNS: &str =
+  "name,default,protection.rule,protection.push,protection.merge,protection.allow_force_push";
+const PER_PAGE: usize = 100;
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
 #[api(
   endpoint(
     route = "/projects/{id}/repository/branches",
     query_struct = "BranchQuery",
     multiple_results,
+    extra_action = "output_branches",
     cli_route = "/projects/{id}/repository/branches",
     cli_help = "Get a list of repository branches from a project, sorted by name alphabetically.",
+    cli_long_help = "Get a list of repository branches from a project, sorted by name alphabetically. With `--protected`, the rules of the protected branches are shown next to them: who can push and merge, and whether the force push is allowed.",
   ),
   endpoint(
     route = "/projects/{id}/repository/branches/{branch}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/branches/{branch}",
     cli_help = "Get a single project repository branch.",
   ),
@@ -22,6 +38,7 @@ use crate::commit::Commit;
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "BranchCreatePayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/branches/create",
     cli_help = "Create a new branch in the repository.",
   ),
@@ -56,6 +73,110 @@ pub struct Branch {
   web_url: String,
   #[api(table_skip)]
   commit: Commit,
+  /// The protected branch rule matching the branch, joined by `--protected`.
+  #[api(table_skip)]
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  protection: Option<BranchProtection>,
+}
+
+/// Summary of a [ProtectedBranch] rule: the access levels are given by their descriptions.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+struct BranchProtection {
+  /// Name of the protected branch, or the wildcard matching the branch.
+  rule: String,
+  push: String,
+  merge: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  unprotect: Option<String>,
+  allow_force_push: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  code_owner_approval_required: Option<bool>,
+}
+
+impl From<&ProtectedBranch> for BranchProtection {
+  fn from(rule: &ProtectedBranch) -> Self {
+    BranchProtection {
+      rule: rule.name.clone(),
+      push: descriptions(&rule.push_access_levels),
+      merge: descriptions(&rule.merge_access_levels),
+      unprotect: rule.unprotect_access_levels.as_deref().map(descriptions),
+      allow_force_push: rule.allow_force_push,
+      code_owner_approval_required: rule.code_owner_approval_required,
+    }
+  }
+}
+
+fn descriptions(access_levels: &[ProtectedAccessLevel]) -> String {
+  access_levels
+    .iter()
+    .map(|access_level| access_level.access_level_description.as_str())
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// `extra_action` of the list of branches: with `--protected`, the branches are printed
+/// with the protected branch rule matching them.
+pub(crate) fn output_branches(branches: &[Branch], settings: &Config) -> Result<()> {
+  let session = Session::current()?;
+  let query = BranchQuery::from_clap_matches(session.subcommands().1)?;
+  // The next pages of `--all` are printed as received.
+  if !query.protected.unwrap_or_default() || pagination::requested(session.subcommands().1) {
+    return output_rows(branches, settings);
+  }
+  let id = session.arg("id").unwrap_or_default();
+  let rules = protected_branches(session, &id)?;
+  let branches: Vec<Branch> = branches
+    .iter()
+    .map(|branch| Branch {
+      protection: matching_rule(&rules, &branch.name).map(BranchProtection::from),
+      ..branch.clone()
+    })
+    .collect();
+  output_joined_rows(&branches, settings, PROTECTED_COLUMNS)
+}
+
+fn protected_branches(session: &Session, id: &str) -> Result<Vec<ProtectedBranch>> {
+  let mut rules = vec![];
+  for page in 1.. {
+    let rules_page: Vec<ProtectedBranch> = session.blocking_get(&format!(
+      "/projects/{id}/protected_branches?per_page={PER_PAGE}&page={page}"
+    ))?;
+    let last = rules_page.len() < PER_PAGE;
+    rules.extend(rules_page);
+    if last {
+      break;
+    }
+  }
+  Ok(rules)
+}
+
+/// The rule protecting the branch `name`: the rule with its exact name, or else the
+/// first wildcard matching it.
+fn matching_rule<'a>(rules: &'a [ProtectedBranch], name: &str) -> Option<&'a ProtectedBranch> {
+  rules
+    .iter()
+    .find(|rule| rule.name == name)
+    .or_else(|| rules.iter().find(|rule| matches_wildcard(&rule.name, name)))
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any characters.
+fn matches_wildcard(pattern: &str, name: &str) -> bool {
+  let mut parts = pattern.split('*');
+  let first = parts.next().unwrap_or_default();
+  let Some(mut rest) = name.strip_prefix(first) else {
+    return false;
+  };
+  let mut parts: Vec<&str> = parts.collect();
+  let Some(last) = parts.pop() else {
+    return rest.is_empty();
+  };
+  for part in parts {
+    match rest.find(part) {
+      Some(index) => rest = &rest[index + part.len()..],
+      None => return false,
+    }
+  }
+  rest.len() >= last.len() && rest.ends_with(last)
 }
 
 #[derive(ApiInput, Serialize, Deserialize, Debug)]
@@ -68,6 +189,14 @@ pub struct BranchQuery {
   )]
   #[serde(skip_serializing_if = "Option::is_none")]
   search: Option<String>,
+
+  #[api(
+    no_short,
+    help = "Show the protected branch rules matching the branches.",
+    long_help = "Show the protected branch rules matching the branches: the access levels allowed to push and merge, and whether the force push is allowed."
+  )]
+  #[serde(skip)]
+  protected: Option<bool>,
 }
 
 #[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
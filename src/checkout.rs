@@ -0,0 +1,38 @@
+//! Project and branch of the git working tree the CLI is run from.
+
+use std::process::Command;
+
+/// Path of the project (`group/subgroup/project`) of the `origin` remote.
+pub(crate) fn origin_project() -> Option<String> {
+  project_path(&git(&["remote", "get-url", "origin"])?)
+}
+
+/// Name of the checked out branch. `None` when the `HEAD` is detached.
+pub(crate) fn current_branch() -> Option<String> {
+  git(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+}
+
+fn git(args: &[&str]) -> Option<String> {
+  let output = Command::new("git").args(args).output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  let out = String::from_utf8(output.stdout).ok()?;
+  let out = out.trim();
+  (!out.is_empty()).then(|| out.to_string())
+}
+
+/// Extracts the project path from the URL of a remote.
+///
+/// The URL can be `git@host:group/project.git`, `ssh://git@host:2222/group/project.git`
+/// or `https://host/group/project.git`.
+fn project_path(url: &str) -> Option<String> {
+  let path = match url.split_once("://") {
+    Some((_, rest)) => rest.split_once('/')?.1,
+    // scp-like syntax.
+    None => url.split_once(':')?.1,
+  };
+  let path = path.trim_matches('/');
+  let path = path.strip_suffix(".git").unwrap_or(path);
+  (!path.is_empty()).then(|| path.to_string())
+}
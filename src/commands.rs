@@ -0,0 +1,133 @@
+//! Commands that are not one call to an endpoint: `config`, `variables export` and
+//! `import`, `repository commits create`, `repository tree --download`,
+//! `jobs {job_id} trace --follow` and `releases create_from_changelog`.
+//!
+//! They are declared among the commands generated by crud-api, for the help and the
+//! completions, but `main` runs them instead of the generated dispatch. The arguments are
+//! first parsed by [parse], with these commands only: the other commands, and the errors,
+//! are left to the generated dispatch.
+
+use crate::{
+  commit::create,
+  jobs::{trace, TraceQuery},
+  output, pagination, profiles,
+  projects::variables::transfer,
+  releases,
+  repository::tree::{download, TreeFilter},
+  retry,
+};
+use clap::{Arg, ArgMatches, Command};
+use crud_api::ApiInput;
+use crud_auth::CrudAuth;
+
+/// Commands run by `main`.
+pub(crate) enum Custom {
+  Config,
+  ExportVariables,
+  ImportVariables,
+  CreateCommit,
+  DownloadTree,
+  FollowTrace,
+  CreateReleaseFromChangelog,
+}
+
+/// Arguments of all the commands besides the generated ones, and the `config` command.
+pub(crate) fn globals(command: Command) -> Command {
+  retry::declare(command).subcommand(profiles::config_command())
+}
+
+/// Adds the commands run by `main` to the generated commands. `tree --download` and
+/// `trace --follow` are options of generated commands.
+pub(crate) fn declare(command: Command) -> Command {
+  command.mut_subcommand("projects", |projects| {
+    projects
+      .mut_subcommand("variables", |variables| {
+        variables.subcommands(transfer::commands())
+      })
+      .mut_subcommand("repository", |repository| {
+        repository.mut_subcommand("commits", |commits| commits.subcommand(create::command()))
+      })
+      .mut_subcommand("releases", |command| {
+        command.subcommand(releases::create_from_changelog_command())
+      })
+  })
+}
+
+/// Options added to the commands once they are all declared.
+pub(crate) fn init(command: Command) -> Command {
+  profiles::apply_defaults(output::declare(pagination::declare(command)))
+}
+
+/// Command run by `main` given by the arguments, with their matches.
+///
+/// `None` when the arguments are not one of these commands or are not valid: they are
+/// parsed again by the generated dispatch.
+pub(crate) fn parse() -> Option<(Custom, ArgMatches)> {
+  let matches = commands().try_get_matches().ok()?;
+  let mut names = vec![];
+  let mut leaf = &matches;
+  while let Some((name, sub_matches)) = leaf.subcommand() {
+    names.push(name);
+    leaf = sub_matches;
+  }
+  let custom = match names.as_slice() {
+    ["config", ..] => Custom::Config,
+    ["projects", "variables", "export"] => Custom::ExportVariables,
+    ["projects", "variables", "import"] => Custom::ImportVariables,
+    ["projects", "repository", "commits", "create"] => Custom::CreateCommit,
+    ["projects", "repository", "tree"] if download::download_requested(leaf) => Custom::DownloadTree,
+    ["projects", "jobs", "trace"] if trace::follow_requested(leaf) => Custom::FollowTrace,
+    ["projects", "releases", "create_from_changelog"] => Custom::CreateReleaseFromChangelog,
+    _ => return None,
+  };
+  Some((custom, matches))
+}
+
+/// Root command with the same arguments as the generated one, without its subcommands.
+pub(crate) fn root() -> Command {
+  let command = crud_api::cli::init_clap()
+    .arg(Arg::new("profile").long("profile"))
+    .arg(Arg::new("base_url").long("base-url"));
+  globals(crud_auth_bearer::Auth::default().clap_auth(command))
+}
+
+/// Matches of the arguments with the root command, the subcommands being left unparsed.
+///
+/// `None` when the arguments are not valid.
+pub(crate) fn pre_parse() -> Option<ArgMatches> {
+  root()
+    .allow_external_subcommands(true)
+    .defer(profiles::apply_defaults)
+    .try_get_matches()
+    .ok()
+}
+
+/// The commands run by `main`, declared with the same arguments as the generated ones.
+fn commands() -> Command {
+  let tree = crud_api::clap_output_format_decl(
+    TreeFilter::clap(Command::new("tree"), None),
+    None,
+    "format",
+    'f',
+    "Formatting",
+  );
+  let trace = TraceQuery::clap(Command::new("trace"), None)
+    .arg(Arg::new("output_file").long("output").short('o'));
+  let projects = Command::new("projects")
+    .subcommand_precedence_over_arg(true)
+    .arg(Arg::new("id"))
+    .subcommand(Command::new("variables").subcommands(transfer::commands()))
+    .subcommand(
+      Command::new("repository")
+        .subcommand(tree)
+        .subcommand(Command::new("commits").subcommand(create::command())),
+    )
+    .subcommand(
+      Command::new("jobs")
+        .subcommand_precedence_over_arg(true)
+        .arg(Arg::new("job_id"))
+        .subcommand(trace),
+    )
+    .subcommand(Command::new("releases").subcommand(releases::create_from_changelog_command()));
+  root().subcommand(projects).defer(init)
+}
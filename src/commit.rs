@@ -1,3 +1,6 @@
+pub(crate) mod create;
+
+use crate::user::User;
 use crud_api::{Api, ApiInput};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
@@ -6,11 +9,47 @@ use std::fmt::Display;
 #[api(endpoint(
   route = "/projects/{id}/merge_requests/{iid}/commits",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/projects/{id}/merge_requests/{iid}/commits",
   cli_help = "Get a list of merge request commits.",
 ))]
+#[api(
+  endpoint(
+    route = "/projects/{id}/repository/commits",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "CommitFilter",
+    cli_route = "/projects/{id}/repository/commits",
+    cli_help = "Get a list of repository commits in a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/repository/commits/{sha}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/repository/commits/{sha}",
+    cli_help = "Get a specific commit identified by the commit hash or name of a branch or tag.",
+  ),
+  endpoint(
+    route = "/projects/{id}/repository/commits/{sha}/cherry_pick",
+    method = "POST",
+    payload_struct = "CherryPickPayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/repository/commits/{sha}/cherry_pick",
+    cli_help = "Cherry-picks a commit to a given branch.",
+  ),
+  endpoint(
+    route = "/projects/{id}/repository/commits/{sha}/revert",
+    method = "POST",
+    payload_struct = "RevertPayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/repository/commits/{sha}/revert",
+    cli_help = "Reverts a commit in a given branch.",
+  )
+)]
 #[api(endpoint(
   route = "/projects/{id}/repository/merge_base",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/repository/merge_base",
   query_struct = "MergeBaseQuery",
   cli_help = "Get the common ancestor for 2 or more refs (commit SHAs, branch names or tags).",
@@ -51,6 +90,17 @@ pub(crate) struct Commit {
   #[api(table_skip)]
   #[serde(skip_serializing_if = "Option::is_none")]
   web_url: Option<String>,
+  /// Only given by the details of a commit, or by the list with `--with-stats`.
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  stats: Option<CommitStats>,
+}
+
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub(crate) struct CommitStats {
+  additions: u32,
+  deletions: u32,
+  total: u32,
 }
 
 impl Display for Commit {
@@ -68,3 +118,302 @@ pub(crate) struct MergeBaseQuery {
   )]
   refs: Vec<String>,
 }
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct CommitFilter {
+  #[api(
+    no_short,
+    long = "ref-name",
+    heading = "Filters",
+    help = "The name of a repository branch, tag or revision range.",
+    long_help = "The name of a repository branch, tag or revision range (`main..feature`). Default: the default branch."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  ref_name: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "Only commits after or on this date are returned.",
+    long_help = "Only commits after or on this date are returned in ISO 8601 format `YYYY-MM-DDTHH:MM:SSZ`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  since: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "Only commits before or on this date are returned.",
+    long_help = "Only commits before or on this date are returned in ISO 8601 format `YYYY-MM-DDTHH:MM:SSZ`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  until: Option<String>,
+
+  #[api(no_short, heading = "Filters", help = "The file path.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  path: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "Search commits by commit author."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  author: Option<String>,
+
+  #[api(
+    no_short,
+    long = "first-parent",
+    heading = "Filters",
+    help = "Follow only the first parent commit upon seeing a merge commit."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  first_parent: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "with-stats",
+    help = "Stats about each commit are added to the response."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  with_stats: Option<bool>,
+
+  #[api(
+    no_short,
+    help = "List commits in order.",
+    long_help = "List commits in order. Possible values: `default`, `topo`. Defaults to `default`, the commits are shown in reverse chronological order.",
+    possible_values = "default,topo"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  order: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct CherryPickPayload {
+  #[api(no_short, help = "The name of the branch.")]
+  branch: String,
+
+  #[api(
+    no_short,
+    long = "dry-run",
+    help = "Does not commit any changes.",
+    long_help = "Does not commit any changes. Default is false."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  dry_run: Option<bool>,
+
+  #[api(no_short, help = "A custom commit message to use for the new commit.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  message: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct RevertPayload {
+  #[api(no_short, help = "Target branch name.")]
+  branch: String,
+
+  #[api(
+    no_short,
+    long = "dry-run",
+    help = "Does not commit any changes.",
+    long_help = "Does not commit any changes. Default is false."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  dry_run: Option<bool>,
+}
+
+#[derive(Api, Clone, Default, Debug, Serialize, Deserialize)]
+#[api(endpoint(
+  route = "/projects/{id}/repository/commits/{sha}/refs",
+  multiple_results,
+  extra_action = "output_rows",
+  query_struct = "CommitRefFilter",
+  cli_route = "/projects/{id}/repository/commits/{sha}/refs",
+  cli_help = "Get all references (from branches or tags) a commit is pushed to.",
+))]
+pub(crate) struct CommitRef {
+  #[serde(rename = "type")]
+  _type: String,
+  name: String,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct CommitRefFilter {
+  #[api(
+    no_short,
+    long = "type",
+    heading = "Filters",
+    help = "The scope of commits.",
+    long_help = "The scope of commits. Possible values `branch`, `tag`, `all`. Default is `all`.",
+    possible_values = "branch,tag,all"
+  )]
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  _type: Option<String>,
+}
+
+#[derive(Api, Clone, Default, Debug, Serialize, Deserialize)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/repository/commits/{sha}/statuses",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "CommitStatusFilter",
+    cli_route = "/projects/{id}/repository/commits/{sha}/statuses",
+    cli_help = "List the statuses of a commit in a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/statuses/{sha}",
+    method = "POST",
+    payload_struct = "CommitStatusCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/repository/commits/{sha}/statuses/create",
+    cli_help = "Adds or updates a build status of a commit.",
+    cli_long_help = "Adds or updates a build status of a commit: the way an external CI reports its results. The status with the same `--name` and `--ref` is updated.",
+  )
+)]
+pub(crate) struct CommitStatus {
+  id: u32,
+  #[api(table_skip)]
+  sha: String,
+  #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+  _ref: Option<String>,
+  status: String,
+  name: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  target_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  coverage: Option<f64>,
+  #[api(table_skip)]
+  allow_failure: bool,
+  created_at: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  started_at: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  finished_at: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  author: Option<User>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct CommitStatusFilter {
+  #[api(
+    no_short,
+    long = "ref",
+    heading = "Filters",
+    help = "The name of a repository branch or tag.",
+    long_help = "The name of a repository branch or tag. Default: the default branch."
+  )]
+  #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+  _ref: Option<String>,
+
+  #[api(no_short, heading = "Filters", help = "Filter by build stage.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  stage: Option<String>,
+
+  #[api(no_short, heading = "Filters", help = "Filter by job name.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct CommitStatusCreatePayload {
+  #[api(
+    no_short,
+    help = "The state of the status.",
+    possible_values = "pending,running,success,failed,canceled"
+  )]
+  state: String,
+
+  #[api(
+    no_short,
+    long = "ref",
+    help = "The ref (branch or tag) to which the status refers."
+  )]
+  #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+  _ref: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The label to differentiate this status from the status of other systems.",
+    long_help = "The label to differentiate this status from the status of other systems. Default value is `default`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    long = "target-url",
+    help = "The target URL to associate with this status."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  target_url: Option<String>,
+
+  #[api(no_short, help = "The short description of the status.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+
+  #[api(no_short, help = "The total code coverage.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  coverage: Option<f64>,
+
+  #[api(
+    no_short,
+    long = "pipeline-id",
+    help = "The ID of the pipeline to set status.",
+    long_help = "The ID of the pipeline to set status. Use in case of several pipeline on same SHA."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pipeline_id: Option<u32>,
+}
@@ -0,0 +1,412 @@
+//! `repository commits create`: one commit with the changes of several files, without
+//! a clone.
+//!
+//! The actions of the commit are read from local paths (`--path`) and the paths to delete
+//! (`--delete`), or from the changes staged in the git working tree (`--staged`).
+
+use crate::{ids::encode, session::Session};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ArgMatches;
+use crud_api::ApiInput;
+use hyper::Method;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+  fmt::Display,
+  path::{Component, Path},
+  process::Command,
+};
+
+/// Modes of the files that can be committed. The symbolic links (`120000`) and the
+/// submodules (`160000`) can't.
+const FILE_MODE: &str = "100644";
+const EXECUTABLE_MODE: &str = "100755";
+
+/// Options of `commits create`. They are not sent to Gitlab as they are.
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct CommitCreateQuery {
+  #[api(
+    no_short,
+    help = "Name of the branch to commit into.",
+    long_help = "Name of the branch to commit into. To create a new branch, also provide `--start-branch`."
+  )]
+  #[serde(skip)]
+  branch: String,
+
+  #[api(no_short, long = "commit-message", help = "Commit message.")]
+  #[serde(skip)]
+  commit_message: String,
+
+  #[api(
+    no_short,
+    long = "start-branch",
+    help = "Name of the branch to start the new branch from."
+  )]
+  #[serde(skip)]
+  start_branch: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Changes",
+    help = "Local file or directory to commit, with the same path in the repository.",
+    long_help = "Local file or directory to commit, with the same path in the repository. The paths are relative to the current directory, which is the root of the repository outside of a git working tree. A file is created or updated whether it is in the branch. Can be repeated."
+  )]
+  #[serde(skip)]
+  path: Option<Vec<String>>,
+
+  #[api(
+    no_short,
+    heading = "Changes",
+    help = "File to delete in the repository.",
+    long_help = "File to delete in the repository. The paths are relative to the current directory, like the paths of `--path`. Can be repeated."
+  )]
+  #[serde(skip)]
+  delete: Option<Vec<String>>,
+
+  #[api(
+    no_short,
+    heading = "Changes",
+    help = "Commit the changes staged in the git working tree: `git diff --cached`.",
+    long_help = "Commit the changes staged in the git working tree: `git diff --cached`. The new, modified, deleted and renamed files, and the changes of their executable bit, are committed."
+  )]
+  #[serde(skip)]
+  staged: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "author-email",
+    help = "Specify the commit author's email address."
+  )]
+  #[serde(skip)]
+  author_email: Option<String>,
+
+  #[api(
+    no_short,
+    long = "author-name",
+    help = "Specify the commit author's name."
+  )]
+  #[serde(skip)]
+  author_name: Option<String>,
+
+  #[api(
+    no_short,
+    long = "dry-run",
+    help = "Show the actions of the commit without committing them."
+  )]
+  #[serde(skip)]
+  dry_run: Option<bool>,
+}
+
+/// Declaration of `repository commits create`.
+pub(crate) fn command() -> clap::Command {
+  CommitCreateQuery::clap(
+    clap::Command::new("create")
+      .about("Create a commit with several files, from local paths or from the changes staged in git.")
+      .long_about("Create a commit with several files, without a clone: the files given by `--path` are created or updated and the files given by `--delete` are deleted, or the changes staged in the git working tree (`--staged`) are committed, moves and changes of the executable bit included. The actions are shown before the commit, and only shown with `--dry-run`."),
+    None,
+  )
+}
+
+/// Payload of `POST /projects/{id}/repository/commits`.
+#[derive(Debug, Serialize)]
+struct CommitPayload<'a> {
+  branch: &'a str,
+  commit_message: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  start_branch: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  author_email: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  author_name: Option<&'a str>,
+  actions: &'a [Action],
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Action {
+  action: &'static str,
+  file_path: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  previous_path: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  content: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  encoding: Option<&'static str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  execute_filemode: Option<bool>,
+}
+
+impl Action {
+  fn new(action: &'static str, file_path: &str) -> Self {
+    Action {
+      action,
+      file_path: file_path.to_string(),
+      ..Default::default()
+    }
+  }
+
+  /// The content is sent as text, or else encoded in base64.
+  fn with_content(mut self, content: Vec<u8>) -> Self {
+    match String::from_utf8(content) {
+      Ok(text) => self.content = Some(text),
+      Err(err) => {
+        self.content = Some(STANDARD.encode(err.into_bytes()));
+        self.encoding = Some("base64");
+      }
+    }
+    self
+  }
+
+  fn chmod(file_path: &str, executable: bool) -> Self {
+    Action {
+      execute_filemode: Some(executable),
+      ..Action::new("chmod", file_path)
+    }
+  }
+}
+
+impl Display for Action {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match (&self.previous_path, self.execute_filemode) {
+      (Some(previous_path), _) => write!(f, "move    {previous_path} -> {}", self.file_path),
+      (None, Some(true)) => write!(f, "chmod   +x {}", self.file_path),
+      (None, Some(false)) => write!(f, "chmod   -x {}", self.file_path),
+      (None, None) => write!(f, "{:<8}{}", self.action, self.file_path),
+    }
+  }
+}
+
+/// Commits the changes given by `--path` or `--staged` to the project `id`.
+pub(crate) async fn run(session: &Session, matches: &ArgMatches) -> Result<i32> {
+  let id = session.arg("id").unwrap_or_default();
+  let options = CommitCreateQuery::from_clap_matches(matches)?;
+  let paths = options.path.as_deref().unwrap_or_default();
+  let deleted = options.delete.as_deref().unwrap_or_default();
+  let actions = match (
+    paths.is_empty() && deleted.is_empty(),
+    options.staged.unwrap_or(false),
+  ) {
+    (false, false) => {
+      let base = options.start_branch.as_deref().unwrap_or(&options.branch);
+      path_actions(session, &id, base, paths, deleted).await?
+    }
+    (true, true) => staged_actions()?,
+    _ => {
+      return Err(miette!(
+        "Give the changes to commit: either --path and --delete, or --staged."
+      ))
+    }
+  };
+  if actions.is_empty() {
+    return Err(miette!("Nothing to commit."));
+  }
+  for action in &actions {
+    println!("{action}");
+  }
+  if options.dry_run.unwrap_or(false) {
+    println!("Dry run: nothing is committed.");
+    return Ok(0);
+  }
+
+  let payload = CommitPayload {
+    branch: &options.branch,
+    commit_message: &options.commit_message,
+    start_branch: options.start_branch.as_deref(),
+    author_email: options.author_email.as_deref(),
+    author_name: options.author_name.as_deref(),
+    actions: &actions,
+  };
+  let response = session
+    .send_json(
+      Method::POST,
+      &format!("/projects/{id}/repository/commits"),
+      Some(&payload),
+    )
+    .await
+    .wrap_err_with(|| format!("Can't commit to the branch {}", options.branch))?;
+  let commit: Value = serde_json::from_slice(&response.body)
+    .into_diagnostic()
+    .wrap_err("Can't deserialize the response")?;
+  println!(
+    "Committed {} to {}: {}",
+    commit["short_id"].as_str().unwrap_or_default(),
+    options.branch,
+    commit["web_url"].as_str().unwrap_or_default()
+  );
+  Ok(0)
+}
+
+/// Actions of the local `paths`, the files of the directories being committed, and of the
+/// `deleted` paths.
+///
+/// A file is created or updated whether it is found in the branch `base`.
+async fn path_actions(
+  session: &Session,
+  id: &str,
+  base: &str,
+  paths: &[String],
+  deleted: &[String],
+) -> Result<Vec<Action>> {
+  // Path of the current directory in the git working tree.
+  let prefix = git(&["rev-parse", "--show-prefix"])
+    .map(|prefix| String::from_utf8_lossy(&prefix).trim().to_string())
+    .unwrap_or_default();
+  let mut files = vec![];
+  for path in paths {
+    local_files(Path::new(path), &mut files)?;
+  }
+
+  let mut actions = vec![];
+  for (local, file_path) in files {
+    let file_path = format!("{prefix}{file_path}");
+    let content = std::fs::read(&local)
+      .into_diagnostic()
+      .wrap_err_with(|| format!("Can't read {}", local.display()))?;
+    let route = format!(
+      "/projects/{id}/repository/files/{}?ref={}",
+      encode(&file_path),
+      encode(base)
+    );
+    let action = if session.exists(&route).await? {
+      "update"
+    } else {
+      "create"
+    };
+    actions.push(Action::new(action, &file_path).with_content(content));
+  }
+  for path in deleted {
+    let file_path = repository_path(Path::new(path))?;
+    actions.push(Action::new("delete", &format!("{prefix}{file_path}")));
+  }
+  Ok(actions)
+}
+
+/// Adds the local file `path`, or the files of the directory `path`, with their path
+/// in the repository.
+fn local_files(path: &Path, files: &mut Vec<(std::path::PathBuf, String)>) -> Result<()> {
+  if !path.exists() {
+    return Err(miette!(
+      "{} is not a local file: the files to delete are given by --delete.",
+      path.display()
+    ));
+  }
+  if path.is_dir() {
+    let mut entries: Vec<_> = std::fs::read_dir(path)
+      .into_diagnostic()
+      .wrap_err_with(|| format!("Can't read the directory {}", path.display()))?
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<Result<_, _>>()
+      .into_diagnostic()?;
+    entries.sort();
+    for entry in entries {
+      if entry.file_name().is_some_and(|name| name != ".git") {
+        local_files(&entry, files)?;
+      }
+    }
+    return Ok(());
+  }
+  files.push((path.to_path_buf(), repository_path(path)?));
+  Ok(())
+}
+
+/// Path in the repository of the relative `path`: `docs/README.md`.
+fn repository_path(path: &Path) -> Result<String> {
+  let mut file_path = vec![];
+  for component in path.components() {
+    match component {
+      Component::Normal(name) => file_path.push(name.to_string_lossy()),
+      Component::CurDir => {}
+      _ => {
+        return Err(miette!(
+          "{} is not a path inside the repository.",
+          path.display()
+        ))
+      }
+    }
+  }
+  Ok(file_path.join("/"))
+}
+
+/// Actions of the changes staged in the git working tree.
+fn staged_actions() -> Result<Vec<Action>> {
+  let diff = git(&["diff", "--cached", "--raw", "-z", "-M", "--no-abbrev"])?;
+  let diff = String::from_utf8(diff)
+    .into_diagnostic()
+    .wrap_err("The staged paths are not UTF-8")?;
+  let mut fields = diff.split('\0').filter(|field| !field.is_empty());
+  let mut actions = vec![];
+  // Each change is `:old_mode new_mode old_sha new_sha status`, then its path, or its
+  // old and new paths when it is renamed.
+  while let Some(change) = fields.next() {
+    let change: Vec<&str> = change.trim_start_matches(':').split(' ').collect();
+    let [old_mode, new_mode, old_sha, new_sha, status] = change[..] else {
+      return Err(miette!("Unexpected output of git diff: {change:?}"));
+    };
+    let path = fields
+      .next()
+      .ok_or_else(|| miette!("Unexpected output of git diff: no path"))?;
+    for mode in [old_mode, new_mode] {
+      if !["000000", FILE_MODE, EXECUTABLE_MODE].contains(&mode) {
+        return Err(miette!(
+          "{path}: the symbolic links and the submodules can't be committed."
+        ));
+      }
+    }
+    let executable = new_mode == EXECUTABLE_MODE;
+    match status.chars().next() {
+      Some('A') => {
+        actions.push(Action::new("create", path).with_content(blob(new_sha)?));
+        if executable {
+          actions.push(Action::chmod(path, true));
+        }
+      }
+      Some('M') => {
+        if old_sha != new_sha {
+          actions.push(Action::new("update", path).with_content(blob(new_sha)?));
+        }
+        if old_mode != new_mode {
+          actions.push(Action::chmod(path, executable));
+        }
+      }
+      Some('D') => actions.push(Action::new("delete", path)),
+      Some('R') => {
+        let new_path = fields
+          .next()
+          .ok_or_else(|| miette!("Unexpected output of git diff: no new path"))?;
+        let mut action = Action::new("move", new_path);
+        action.previous_path = Some(path.to_string());
+        if old_sha != new_sha {
+          action = action.with_content(blob(new_sha)?);
+        }
+        actions.push(action);
+        if old_mode != new_mode {
+          actions.push(Action::chmod(new_path, executable));
+        }
+      }
+      _ => return Err(miette!("{path}: unsupported change {status}.")),
+    }
+  }
+  Ok(actions)
+}
+
+/// Content of the staged file `sha`.
+fn blob(sha: &str) -> Result<Vec<u8>> {
+  git(&["cat-file", "blob", sha])
+}
+
+fn git(args: &[&str]) -> Result<Vec<u8>> {
+  let output = Command::new("git")
+    .args(args)
+    .output()
+    .into_diagnostic()
+    .wrap_err("Can't run git")?;
+  if !output.status.success() {
+    return Err(miette!(
+      "git {}: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+  Ok(output.stdout)
+}
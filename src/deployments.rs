@@ -0,0 +1,220 @@
+use crate::{commit::Commit, environments::Environment, pipeline::Pipeline, user::User};
+use crud_api::{Api, ApiInput};
+use miette::{IntoDiagnostic, WrapErr};
+use serde::{Deserialize, Serialize};
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/deployments",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "DeploymentFilter",
+    cli_route = "/projects/{id}/deployments",
+    cli_help = "Get a list of deployments in a project.",
+    cli_long_help = "Get a list of deployments in a project. The last deployment to production is given by `--environment production --status success --order-by finished_at --sort desc --per-page 1`."
+  ),
+  endpoint(
+    route = "/projects/{id}/deployments/{deployment_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deployments/{deployment_id}",
+    cli_help = "Get a specific deployment.",
+    cli_long_help = "Get a specific deployment with its job, pipeline and commit."
+  )
+)]
+pub(crate) struct Deployment {
+  id: u32,
+  iid: u32,
+  #[serde(rename = "ref")]
+  _ref: String,
+  #[api(table_skip)]
+  sha: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  status: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  environment: Option<Environment>,
+  #[api(table_format(date(format = "%Y-%m-%d %H:%M:%S")))]
+  created_at: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  updated_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user: Option<User>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  deployable: Option<Deployable>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pending_approval_count: Option<u32>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  approvals: Option<Vec<DeploymentApproval>>,
+}
+
+/// The job that ran the deployment.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct Deployable {
+  id: u32,
+  name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  stage: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  status: Option<String>,
+  #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+  _ref: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tag: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  created_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  started_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  finished_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  web_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  commit: Option<Commit>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pipeline: Option<Pipeline>,
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(endpoint(
+  route = "/projects/{id}/deployments/{deployment_id}/approval",
+  method = "POST",
+  payload_struct = "DeploymentApprovalPayload",
+  extra_action = "output_result",
+  cli_route = "/projects/{id}/deployments/{deployment_id}/approval",
+  cli_help = "Approve or reject a blocked deployment.",
+  cli_long_help = "Approve or reject a deployment waiting for the approval of a protected environment."
+))]
+pub(crate) struct DeploymentApproval {
+  user: User,
+  status: String,
+  #[api(table_format(date(format = "%Y-%m-%d %H:%M:%S")))]
+  created_at: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  comment: Option<String>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct DeploymentFilter {
+  #[api(
+    no_short,
+    heading = "Filter",
+    help = "The name of the environment to filter deployments by."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  environment: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filter",
+    help = "The status to filter deployments by.",
+    possible_values = "created,running,success,failed,canceled,blocked"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  status: Option<String>,
+
+  #[api(
+    no_short,
+    long = "updated-after",
+    heading = "Filter",
+    help = "Return deployments updated after the specified date.",
+    long_help = "Return deployments updated after the specified date. Expected in ISO 8601 format (2019-03-15T08:00:00Z)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  updated_after: Option<String>,
+
+  #[api(
+    no_short,
+    long = "updated-before",
+    heading = "Filter",
+    help = "Return deployments updated before the specified date.",
+    long_help = "Return deployments updated before the specified date. Expected in ISO 8601 format (2019-03-15T08:00:00Z)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  updated_before: Option<String>,
+
+  #[api(
+    no_short,
+    long = "finished-after",
+    heading = "Filter",
+    help = "Return deployments finished after the specified date.",
+    long_help = "Return deployments finished after the specified date. Expected in ISO 8601 format (2019-03-15T08:00:00Z). Requires `--order-by finished_at` and `--status success`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  finished_after: Option<String>,
+
+  #[api(
+    no_short,
+    long = "finished-before",
+    heading = "Filter",
+    help = "Return deployments finished before the specified date.",
+    long_help = "Return deployments finished before the specified date. Expected in ISO 8601 format (2019-03-15T08:00:00Z). Requires `--order-by finished_at` and `--status success`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  finished_before: Option<String>,
+
+  #[api(
+    no_short,
+    long = "order-by",
+    heading = "Sorting",
+    help = "Return deployments ordered by the field.",
+    long_help = "Return deployments ordered by either one of `id`, `iid`, `created_at`, `updated_at`, `finished_at` or `ref` fields. Default is `id`.",
+    possible_values = "id,iid,created_at,updated_at,finished_at,ref"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  order_by: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Sorting",
+    help = "The direction of the order.",
+    long_help = "Return deployments sorted in `asc` (default) or `desc` order.",
+    possible_values = "asc,desc"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sort: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct DeploymentApprovalPayload {
+  #[api(
+    no_short,
+    help = "The status of the approval.",
+    possible_values = "approved,rejected"
+  )]
+  status: String,
+
+  #[api(no_short, help = "A comment to go with the approval.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  comment: Option<String>,
+
+  #[api(
+    no_short,
+    long = "represented-as",
+    help = "The name of the user or group to approve as.",
+    long_help = "The name of the user or group to approve as. Used when the user belongs to several approval rules."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  represented_as: Option<String>,
+}
@@ -7,11 +7,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/versions",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/merge_requests/{iid}/versions",
     cli_help = "Get a list of merge request diff versions.",
   ),
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/versions/{verion_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/versions/{verion_id}",
     cli_help = "Get a single merge request diff version.",
   )
@@ -35,14 +37,28 @@ pub(crate) struct DiffVersion {
   diffs: Option<Vec<Change>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Api, Debug, Default, Serialize, Deserialize, Clone)]
+#[api(endpoint(
+  route = "/projects/{id}/repository/commits/{sha}/diff",
+  multiple_results,
+  extra_action = "output_rows",
+  cli_route = "/projects/{id}/repository/commits/{sha}/diff",
+  cli_help = "Get the diff of a commit in a project.",
+  cli_long_help = "Get the diff of a commit in a project. The table lists the changed files: the diffs are given by `-f json`, or by an `--output-template` rendering their `diff` field.",
+))]
 pub(crate) struct Change {
   old_path: String,
   new_path: String,
-  a_mode: String,
-  b_mode: String,
+  /// May be null in the diffs of the commits.
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  a_mode: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  b_mode: Option<String>,
   new_file: bool,
   renamed_file: bool,
   deleted_file: bool,
+  #[api(table_skip)]
   diff: String,
 }
@@ -0,0 +1,250 @@
+use crate::notes::Note;
+use crud_api::{Api, ApiInput};
+use crud_pretty_struct::PrettyPrint;
+use serde::{Deserialize, Serialize};
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions",
+    multiple_results,
+    query_struct = "DiscussionFilter",
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions",
+    cli_help = "Gets a list of all discussion items for a single merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}",
+    cli_help = "Returns a single discussion item for a specific project merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "DiscussionCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions/create",
+    cli_help = "Creates a new thread to a single project merge request.",
+    cli_long_help = "Creates a new thread to a single project merge request. This is similar to creating a note but other comments (replies) can be added to it later.\n\nTo start a thread on a diff, set the position options. The `base`, `start` and `head` SHAs are the `base_commit_sha`, `start_commit_sha` and `head_commit_sha` of the latest merge request version (see `projects {id} merge_requests {iid} versions`)."
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}",
+    method = "PUT",
+    query_struct = "DiscussionResolveQuery",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/resolve",
+    cli_help = "Resolve or unresolve a thread of discussion in a merge request.",
+  )
+)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions",
+    multiple_results,
+    query_struct = "DiscussionFilter",
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/issues/{iid}/discussions",
+    cli_help = "Gets a list of all discussion items for a single issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}",
+    cli_help = "Returns a single discussion item for a specific project issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "DiscussionNotePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/discussions/create",
+    cli_help = "Creates a new thread to a single project issue.",
+    cli_long_help = "Creates a new thread to a single project issue. This is similar to creating a note but other comments (replies) can be added to it later."
+  )
+)]
+#[derive(PrettyPrint)]
+pub(crate) struct Discussion {
+  #[pretty(color = "green")]
+  id: String,
+  #[pretty(skip)]
+  individual_note: bool,
+  #[api(table_skip)]
+  #[pretty(is_pretty)]
+  notes: Vec<Note>,
+}
+
+/// All the threads of an issue or a merge request, displayed as a conversation.
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions",
+    query_struct = "DiscussionFilter",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/conversation",
+    cli_help = "Shows the discussions of a merge request as a conversation.",
+    cli_long_help = "Shows the discussions of a merge request as a conversation: the threads with their notes, one after the other.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions",
+    query_struct = "DiscussionFilter",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/conversation",
+    cli_help = "Shows the discussions of an issue as a conversation.",
+    cli_long_help = "Shows the discussions of an issue as a conversation: the threads with their notes, one after the other.",
+  )
+)]
+#[serde(transparent)]
+#[derive(PrettyPrint)]
+pub(crate) struct Conversation {
+  #[api(table_skip)]
+  #[pretty(is_pretty)]
+  discussions: Vec<Discussion>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct DiscussionFilter {
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct DiscussionResolveQuery {
+  #[api(no_short, help = "Resolve or unresolve the discussion.")]
+  resolved: bool,
+}
+
+/// Position of a diff thread.
+///
+/// The SHAs are the ones of a merge request version (`DiffVersion`).
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct DiffPosition {
+  #[api(
+    no_short,
+    long = "base-sha",
+    heading = "Position",
+    help = "Base commit SHA in the source branch.",
+    long_help = "Base commit SHA in the source branch. This is the `base_commit_sha` of the merge request version."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  base_sha: Option<String>,
+
+  #[api(
+    no_short,
+    long = "start-sha",
+    heading = "Position",
+    help = "SHA referencing commit in target branch.",
+    long_help = "SHA referencing commit in target branch. This is the `start_commit_sha` of the merge request version."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  start_sha: Option<String>,
+
+  #[api(
+    no_short,
+    long = "head-sha",
+    heading = "Position",
+    help = "SHA referencing HEAD of this merge request.",
+    long_help = "SHA referencing HEAD of this merge request. This is the `head_commit_sha` of the merge request version."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  head_sha: Option<String>,
+
+  #[api(
+    no_short,
+    long = "type",
+    heading = "Position",
+    help = "Type of the position reference.",
+    possible_values = "text,image"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  position_type: Option<String>,
+
+  #[api(
+    no_short,
+    long = "old-path",
+    heading = "Position",
+    help = "File path before change."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  old_path: Option<String>,
+
+  #[api(
+    no_short,
+    long = "new-path",
+    heading = "Position",
+    help = "File path after change."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  new_path: Option<String>,
+
+  #[api(
+    no_short,
+    long = "old-line",
+    heading = "Position",
+    help = "Line number before change.",
+    long_help = "Line number before change. Set it alone to comment a removed line; set it with `new-line` to comment an unchanged line."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  old_line: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "new-line",
+    heading = "Position",
+    help = "Line number after change.",
+    long_help = "Line number after change. Set it alone to comment an added line; set it with `old-line` to comment an unchanged line."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  new_line: Option<u32>,
+}
+
+fn is_position_unset(position: &Option<DiffPosition>) -> bool {
+  match position {
+    Some(p) => p.base_sha.is_none() && p.start_sha.is_none() && p.head_sha.is_none(),
+    None => true,
+  }
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct DiscussionCreatePayload {
+  #[api(no_short, help = "The content of the thread.")]
+  body: String,
+
+  #[api(
+    no_short,
+    long = "commit-id",
+    help = "SHA referencing commit to start this thread on."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  commit_id: Option<String>,
+
+  #[api(
+    no_short,
+    long = "created-at",
+    help = "Date time string, ISO 8601 formatted.",
+    long_help = "Date time string, ISO 8601 formatted. Example: 2016-03-11T03:45:40Z (requires administrator or project/group owner rights)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  created_at: Option<String>,
+
+  #[serde(skip_serializing_if = "is_position_unset")]
+  position: Option<DiffPosition>,
+}
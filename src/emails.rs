@@ -6,11 +6,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/user/emails",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/user/emails",
     cli_help = "Get a list of currently authenticated user's emails.",
   ),
   endpoint(
     route = "/user/emails/{id}",
+    extra_action = "output_result",
     cli_route = "/user/emails/{id}",
     cli_help = "Get a single email.",
   ),
@@ -19,6 +21,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "EmailCreatePayload",
+    extra_action = "output_result",
     cli_route = "/user/emails/create",
     cli_help = "Creates a new email owned by the currently authenticated user.",
   ),
@@ -36,6 +39,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/users/{id}/emails",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/users/{id}/emails",
     cli_help = "Get a single email.",
   ),
@@ -44,6 +48,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "EmailCreateWithValidationPayload",
+    extra_action = "output_result",
     cli_route = "/users/{id}/emails/create",
     cli_help = "Create new email owned by specified user.",
   ),
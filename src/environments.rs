@@ -0,0 +1,182 @@
+use crate::deployments::Deployment;
+use crud_api::{Api, ApiInput};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/environments",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "EnvironmentFilter",
+    cli_route = "/projects/{id}/environments",
+    cli_help = "Get all environments for a given project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/environments/{environment_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/environments/{environment_id}",
+    cli_help = "Get a specific environment.",
+    cli_long_help = "Get a specific environment with its last deployment."
+  ),
+  endpoint(
+    route = "/projects/{id}/environments",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "EnvironmentCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/environments/create",
+    cli_help = "Creates a new environment.",
+  ),
+  endpoint(
+    route = "/projects/{id}/environments/{environment_id}",
+    method = "PUT",
+    payload_struct = "EnvironmentUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/environments/{environment_id}/edit",
+    cli_help = "Updates an existing environment.",
+  ),
+  endpoint(
+    route = "/projects/{id}/environments/{environment_id}/stop",
+    method = "POST",
+    payload_struct = "EnvironmentStopPayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/environments/{environment_id}/stop",
+    cli_help = "Stops an environment.",
+  ),
+  endpoint(
+    route = "/projects/{id}/environments/{environment_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    cli_no_output,
+    result_struct = "EmptyResponse",
+    cli_route = "/projects/{id}/environments/{environment_id}/delete",
+    cli_help = "Deletes an environment.",
+    cli_long_help = "Deletes an environment. The environment must be stopped first."
+  )
+)]
+pub(crate) struct Environment {
+  id: u32,
+  name: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  slug: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  external_url: Option<String>,
+  state: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tier: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  created_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  updated_at: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  auto_stop_at: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  last_deployment: Option<Box<Deployment>>,
+}
+
+impl Display for Environment {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.name)
+  }
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct EnvironmentFilter {
+  #[api(
+    no_short,
+    heading = "Filter",
+    help = "Return the environment with this name.",
+    long_help = "Return the environment with this name. Mutually exclusive with `search`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filter",
+    help = "Return list of environments matching the search criteria.",
+    long_help = "Return list of environments matching the search criteria. Mutually exclusive with `name`. Must be at least 3 characters long."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  search: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filter",
+    help = "List all environments that match a specific state.",
+    possible_values = "available,stopping,stopped"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  states: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct EnvironmentCreatePayload {
+  #[api(no_short, help = "The name of the environment.")]
+  name: String,
+
+  #[api(
+    no_short,
+    long = "external-url",
+    help = "Place to link to for this environment."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  external_url: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The tier of the new environment.",
+    possible_values = "production,staging,testing,development,other"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tier: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct EnvironmentUpdatePayload {
+  #[api(no_short, long = "external-url", help = "The new external URL.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  external_url: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The tier of the environment.",
+    possible_values = "production,staging,testing,development,other"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tier: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct EnvironmentStopPayload {
+  #[api(
+    no_short,
+    help = "Force environment to stop without executing `on_stop` actions."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  force: Option<bool>,
+}
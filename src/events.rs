@@ -1,4 +1,4 @@
-use crate::User;
+use crate::{notes::Note, User};
 use crud_api::{Api, ApiInput};
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
     route = "/events",
     query_struct = "EventFilter",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/events",
     cli_help = "Get a list of events for the authenticated user.",
     cli_long_help = "Get a list of events for the authenticated user. Scope read_user or api is required. Events associated with epics are not available using the API.",
@@ -16,6 +17,7 @@ use serde::{Deserialize, Serialize};
     route = "/users/{id}/events",
     query_struct = "EventFilter",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/users/{id}/events",
     cli_help = "Get the contribution events for the specified user,",
     cli_long_help = "Get the contribution events for the specified user, sorted from newest to oldest. Scope read_user or api is required. Events associated with epics are not available using API.",
@@ -24,6 +26,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/events",
     query_struct = "EventFilter",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/events",
     cli_help = "Get a list of visible events for a particular project.",
   )
@@ -85,26 +88,6 @@ struct PushData {
   ref_count: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Note {
-  id: u32,
-  #[serde(rename = "type")]
-  type_: Option<String>,
-  body: String,
-  attachment: Option<String>,
-  author: User,
-  created_at: String,
-  updated_at: String,
-  system: bool,
-  noteable_id: u32,
-  noteable_type: String,
-  resolvable: bool,
-  confidential: bool,
-  internal: bool,
-  noteable_iid: u32,
-  //  commands_changes:{}
-}
-
 #[derive(Debug, ApiInput, Serialize, Deserialize)]
 #[api(no_input_file)]
 pub(crate) struct EventFilter {
@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
   ),
   endpoint(
     route = "/projects/{id}/export",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/export",
     cli_help = "Get the status of export.",
   ),
@@ -42,24 +43,6 @@ use serde::{Deserialize, Serialize};
     cli_help = "Download the finished export.",
   )
 )]
-#[api(
-  endpoint(
-    route = "/groups/{id}/export",
-    method = "POST",
-    cli_no_output,
-    result_ok_status = "ACCEPTED",
-    result_struct = "EmptyResponse",
-    cli_route = "/groups/{id}/export/create",
-    cli_help = "Start a new export.",
-  ),
-  endpoint(
-    route = "/groups/{id}/export/download",
-    stream,
-    cli_route = "/groups/{id}/export/download",
-    cli_help = "Download the finished export.",
-  )
-)]
-
 pub(crate) struct Export {
   id: u32,
   description: Option<String>,
@@ -103,6 +86,7 @@ pub(crate) struct ExportPayload {
   endpoint(
     route = "/groups/{id}/export_relations/status",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/export_relations/status",
     cli_help = "View the status of the relations export",
   ),
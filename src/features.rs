@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/features",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/features",
   cli_help = "Get a list of all persisted features, with its gate values.",
 ))]
@@ -27,10 +28,11 @@ pub enum Gate {
   PercentageOfActors { value: u32 },
 }
 
-#[derive(Api, Serialize, Deserialize, Debug)]
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
 #[api(endpoint(
   route = "/features/definitions",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/features/definitions",
   cli_help = "Get a list of all feature definitions.",
 ))]
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "GroupsFilter",
     cli_route = "/groups",
     cli_help = "Get a list of visible groups for the authenticated user.",
@@ -18,6 +19,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/subgroups",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "GroupsFilter",
     cli_route = "/groups/{id}/subgroups",
     cli_help = "Get a list of visible direct subgroups in this group.",
@@ -26,6 +28,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/descendant_groups",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "GroupsFilter",
     cli_route = "/groups/{id}/descendant_groups",
     cli_help = "Get a list of visible descendant groups of this group.",
@@ -34,6 +37,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}",
     query_struct = "GroupDetail",
+    extra_action = "output_result",
     cli_route = "/groups/{id}",
     cli_help = "Get all details of a group.",
   ),
@@ -47,6 +51,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups",
     method = "POST",
     payload_struct = "GroupsCreatePayload",
+    extra_action = "output_result",
     cli_route = "/groups/create",
     cli_long_help = "Creates a new project group. Available only for users who can create groups.",
     cli_help = "Creates a new project group.",
@@ -55,6 +60,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}",
     method = "PUT",
     payload_struct = "GroupsUpdatePayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/edit",
     cli_long_help = "Updates the project group. Only available to group owners and administrators.",
     cli_help = "Updates the project group.",
@@ -64,6 +70,7 @@ use serde::{Deserialize, Serialize};
     method = "DELETE",
     query_struct = "GroupsDeleteQuery",
     result_ok_status = "ACCEPTED",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/delete",
     cli_long_help = "Removes group, and queues a background job to delete all projects in the group as well. Since GitLab 12.8, on Premium or higher tiers, marks a group for deletion. The deletion happens 7 days later by default, but this can be changed in the instance settings.",
     cli_help = "Removes group, and queues a background job to delete all projects in the group as well.",
@@ -72,12 +79,14 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/restore",
     method = "POST",
     payload_struct = "GroupsSharePayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/restore",
     cli_help = "Restores a group marked for deletion.",
   ),
   endpoint(
     route = "/groups/{id}/projects/{project_id}",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/projects/{project_id}/transfert",
     cli_help = "Transfer a project to the Group namespace.",
   )
@@ -86,6 +95,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/share",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/share",
     cli_help = "Share group with another group.",
   ),
@@ -93,6 +103,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/share/{share_id}",
     method = "DELETE",
     result_ok_status = "ACCEPTED",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/share/{share_id}",
     cli_help = "Unshare the group from another group.",
   )
@@ -103,6 +114,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     query_struct = "GroupTransferQuery",
     result_ok_status = "OK",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/transfer",
     cli_help = "Transfer a group to a new parent group or turn a subgroup to a top-level group.",
   ),
@@ -111,6 +123,7 @@ use serde::{Deserialize, Serialize};
     cli_route = "/groups/{id}/transfer_locations",
     query_struct = "GroupTransferLocationQuery",
     multiple_results,
+    extra_action = "output_rows",
     cli_help = "Retrieve a list of groups to which the user can transfer a project.",
   )
 )]
@@ -6,11 +6,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/hooks",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/hooks",
     cli_help = "Get a list of project hooks.",
   ),
   endpoint(
     route = "/projects/{id}/hooks/{hook_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/hooks/{hook_id}",
     cli_help = "Get a specific hook for a project.",
   ),
@@ -19,6 +21,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "ProjectsHookPayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/hooks/create",
     cli_help = "Adds a hook to a specified project.",
   ),
@@ -26,6 +29,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/hooks/{hook_id}",
     method = "PUT",
     payload_struct = "ProjectsHookPayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/hooks/{hook_id}/edit",
     cli_help = "Edits a hook for a specified project.",
   ),
@@ -44,11 +48,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/hooks",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/hooks",
     cli_help = "Get a list of group hooks",
   ),
   endpoint(
     route = "/groups/{id}/hooks/{hook_id}",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/hooks/{hook_id}",
     cli_help = "Get a specific hook for a group.",
   ),
@@ -57,6 +63,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "GroupsHookPayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/hooks/create",
     cli_help = "Adds a hook to a specified group.",
   ),
@@ -64,6 +71,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/hooks/{hook_id}",
     method = "PUT",
     payload_struct = "GroupsHookPayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/hooks/{hook_id}/edit",
     cli_help = "Edits a hook for a specified groups.",
   ),
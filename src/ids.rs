@@ -0,0 +1,407 @@
+//! Resolution of the ids given in the arguments.
+//!
+//! In `projects {id} ...`, the project can be:
+//! - omitted or given as `.`: it is the project of the `origin` remote of the git
+//!   working tree, or else the default project of the profile,
+//! - a path: `group/subgroup/project`,
+//! - a web URL: `https://gitlab.example.com/group/project/-/merge_requests/12`. Its host
+//!   must be the host of the base URL.
+//!
+//! The merge requests, issues, pipelines and jobs of a project can be given by their
+//! web URL too. The merge request given as `.` is the open merge request of the
+//! current branch.
+//!
+//! In `groups {id} ...`, the group can be a path or a web URL.
+//!
+//! The ids are resolved by [resolve] before the commands are run, with the arguments
+//! after `projects` or `groups` read as they are given. Then [apply] makes the
+//! arguments of the commands parse them as the resolved ids.
+
+use crate::{checkout, commands, profiles, session::Session};
+use clap::{builder::StringValueParser, builder::TypedValueParser, Arg, Command};
+use config::Config;
+use miette::{miette, Result};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// Id of the current project or of the merge request of the current branch.
+const CURRENT: &str = ".";
+
+/// Subcommands of `projects {id}` whose id can be given by a web URL, with the name of
+/// their id argument. The names are the same in the web URLs.
+const RESOURCES: [(&str, &str); 4] = [
+  ("merge_requests", "iid"),
+  ("issues", "iid"),
+  ("pipelines", "pipeline_id"),
+  ("jobs", "job_id"),
+];
+
+/// Characters kept by [encode]: the unreserved characters of the RFC 3986.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+  .remove(b'-')
+  .remove(b'.')
+  .remove(b'_')
+  .remove(b'~');
+
+/// Ids resolved by [resolve].
+static RESOLVED: OnceLock<Vec<Replacement>> = OnceLock::new();
+
+#[derive(Debug, Default, Deserialize)]
+struct BranchMergeRequest {
+  iid: u32,
+}
+
+/// Web URL of a project, a group or a resource of a project.
+#[derive(Debug)]
+struct WebUrl {
+  /// Host, with its port: `gitlab.example.com`.
+  host: String,
+  /// Path of the project or of the group.
+  path: String,
+  /// Name and id of the resource: `("merge_requests", "12")`.
+  resource: Option<(String, String)>,
+}
+
+impl WebUrl {
+  /// Parses `https://gitlab.example.com/group/project/-/merge_requests/12`.
+  fn parse(url: &str) -> Option<WebUrl> {
+    let (_, rest) = url.split_once("://")?;
+    let (host, path) = rest.split_once('/')?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let (path, resource) = match path.split_once("/-/") {
+      Some((path, resource)) => {
+        let mut segments = resource.split('/');
+        let resource = match (segments.next(), segments.next()) {
+          (Some(name), Some(id)) if !id.is_empty() => Some((name.to_string(), id.to_string())),
+          _ => None,
+        };
+        (path, resource)
+      }
+      None => (path, None),
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    (!path.is_empty()).then(|| WebUrl {
+      host: host.to_string(),
+      path: path.to_string(),
+      resource,
+    })
+  }
+
+  /// Web URL given by `value`, when it is one. Fails when its host is not the host of
+  /// the `session`: the id would be looked up in another instance.
+  fn given(value: &str, session: &Session) -> Result<Option<WebUrl>> {
+    match WebUrl::parse(value) {
+      Some(url) if url.host != session.host() => Err(miette!(
+        "`{value}` is a web URL of {}, but the base URL is on {}.",
+        url.host,
+        session.host()
+      )),
+      url => Ok(url),
+    }
+  }
+
+  /// Id of the resource `name` given by the URL.
+  fn resource_id(&self, name: &str) -> Option<String> {
+    self
+      .resource
+      .as_ref()
+      .filter(|(resource, _)| resource == name)
+      .map(|(_, id)| id.clone())
+  }
+}
+
+/// Value of an argument replaced by its resolved id.
+#[derive(Debug)]
+struct Replacement {
+  /// Subcommands of the argument: `["projects", "merge_requests"]`.
+  path: Vec<&'static str>,
+  arg: &'static str,
+  /// Value given in the command line. `None` when the argument is omitted: the id is
+  /// its default value.
+  from: Option<String>,
+  to: String,
+}
+
+impl Replacement {
+  fn apply(&'static self, command: Command) -> Command {
+    mut_arg(command, &self.path, self.arg, |arg| match &self.from {
+      Some(from) => replace_value(arg, from.clone(), self.to.clone()),
+      None => arg.default_value(self.to.as_str()),
+    })
+  }
+}
+
+/// Ids given after `projects`: `projects [ID] [RESOURCE [RESOURCE_ID]] ...`.
+///
+/// The ids are told from the names of the subcommands by their form: a project is
+/// given by a numeric id, a path, a web URL or `.`.
+#[derive(Debug, Default)]
+struct ProjectArgs {
+  id: Option<String>,
+  subcommand: Option<String>,
+  resource_id: Option<String>,
+  /// A subcommand follows the resource.
+  resource_subcommand: bool,
+}
+
+impl ProjectArgs {
+  fn parse(args: &[String]) -> ProjectArgs {
+    // The options are not known here: the ids are read until the first one.
+    let mut tokens = args.iter().take_while(|token| !token.starts_with('-'));
+    let mut next = tokens.next();
+    let id = next.filter(|token| is_id(token)).cloned();
+    if id.is_some() {
+      next = tokens.next();
+    }
+    let subcommand = next.cloned();
+    let mut next = tokens.next();
+    let resource_id = next.filter(|token| is_id(token)).cloned();
+    if resource_id.is_some() {
+      next = tokens.next();
+    }
+    ProjectArgs {
+      id,
+      subcommand,
+      resource_id,
+      resource_subcommand: next.is_some(),
+    }
+  }
+}
+
+/// Resolves the ids given in the arguments that are not numeric ids. They are replaced
+/// in the commands by [apply].
+///
+/// Nothing is resolved when the arguments can't be parsed or the help is asked: the
+/// errors and the help are left to the commands.
+pub(crate) async fn resolve() -> Result<()> {
+  let Some(matches) = commands::pre_parse() else {
+    return Ok(());
+  };
+  let (name, args) = match matches.subcommand() {
+    Some((name, sub_matches)) => {
+      let args: Vec<String> = sub_matches
+        .get_raw("")
+        .into_iter()
+        .flatten()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect();
+      (name, args)
+    }
+    None => return Ok(()),
+  };
+  if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+    return Ok(());
+  }
+  let settings = profiles::settings()?;
+  let session = Session::new(&matches, &settings);
+  let replacements = match name {
+    "projects" => resolve_project(&session, &settings, &ProjectArgs::parse(&args)).await?,
+    "groups" => match args.first().filter(|id| is_id(id)) {
+      Some(id) => namespace_id(id, "groups/", &session)?
+        .map(|group| Replacement {
+          path: vec!["groups"],
+          arg: "id",
+          from: Some(id.clone()),
+          to: group,
+        })
+        .into_iter()
+        .collect(),
+      None => vec![],
+    },
+    _ => vec![],
+  };
+  let _ = RESOLVED.set(replacements);
+  Ok(())
+}
+
+/// Makes the arguments of the commands parse the ids resolved by [resolve].
+pub(crate) fn apply(command: Command) -> Command {
+  match RESOLVED.get() {
+    Some(replacements) => replacements
+      .iter()
+      .fold(command, |command, replacement| replacement.apply(command)),
+    None => command,
+  }
+}
+
+async fn resolve_project(
+  session: &Session,
+  settings: &Config,
+  args: &ProjectArgs,
+) -> Result<Vec<Replacement>> {
+  // The subcommand of the project, with the name of its id argument.
+  let resource = args
+    .subcommand
+    .as_deref()
+    .and_then(|name| RESOURCES.iter().find(|(resource, _)| *resource == name));
+  let resource_value = resource.and(args.resource_id.as_ref());
+  // The web URL of a resource gives its project too.
+  let resource_url = match resource_value {
+    Some(value) => WebUrl::given(value, session)?,
+    None => None,
+  };
+  let project_url = match &args.id {
+    Some(id) => WebUrl::given(id, session)?,
+    None => None,
+  };
+
+  let mut replacements = vec![];
+  let mut replace_project = |from: Option<&String>, project: &String| {
+    replacements.push(Replacement {
+      path: vec!["projects"],
+      arg: "id",
+      from: from.cloned(),
+      to: project.clone(),
+    })
+  };
+  let project = match &args.id {
+    Some(id) if id == CURRENT => {
+      let project = resource_url
+        .as_ref()
+        .map(|url| encode(&url.path))
+        .or_else(|| current_project(settings))
+        .ok_or_else(|| {
+          miette!(
+            "`.` is the project of the git working tree, but no `origin` remote was found and the profile has no default project."
+          )
+        })?;
+      replace_project(Some(id), &project);
+      project
+    }
+    Some(id) => match namespace_id(id, "", session)? {
+      Some(project) => {
+        replace_project(Some(id), &project);
+        project
+      }
+      None => id.clone(),
+    },
+    None if args.subcommand.is_some() => {
+      let project = resource_url
+        .as_ref()
+        .map(|url| encode(&url.path))
+        .or_else(|| current_project(settings));
+      match project {
+        Some(project) => {
+          replace_project(None, &project);
+          project
+        }
+        // The generated dispatch reports the missing id.
+        None => return Ok(replacements),
+      }
+    }
+    None => return Ok(replacements),
+  };
+
+  let Some(&(name, arg)) = resource else {
+    return Ok(replacements);
+  };
+  let resolved = match resource_value {
+    Some(value) if value == CURRENT && name == "merge_requests" => {
+      let iid = branch_merge_request(session, &project).await?;
+      Some((Some(value.clone()), iid.to_string()))
+    }
+    Some(value) => match &resource_url {
+      Some(url) => {
+        let id = url.resource_id(name).ok_or_else(|| {
+          miette!("`{value}` is not the web URL of one of the {name} of a project.")
+        })?;
+        Some((Some(value.clone()), id))
+      }
+      None => None,
+    },
+    // `projects <merge request URL> merge_requests approvals`
+    None if args.resource_subcommand => project_url
+      .and_then(|url| url.resource_id(name))
+      .map(|id| (None, id)),
+    None => None,
+  };
+  if let Some((from, to)) = resolved {
+    replacements.push(Replacement {
+      path: vec!["projects", name],
+      arg,
+      from,
+      to,
+    });
+  }
+  Ok(replacements)
+}
+
+/// `true` when the argument is an id rather than the name of a subcommand.
+fn is_id(arg: &str) -> bool {
+  arg == CURRENT
+    || arg.contains(['/', '%'])
+    || (!arg.is_empty() && arg.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Updates the argument `arg` of the subcommand given by `path`, when they are declared.
+fn mut_arg(
+  command: Command,
+  path: &[&str],
+  arg: &'static str,
+  update: impl FnOnce(Arg) -> Arg,
+) -> Command {
+  match path.split_first() {
+    Some((name, path)) if command.find_subcommand(name).is_some() => {
+      command.mut_subcommand(name, |subcommand| mut_arg(subcommand, path, arg, update))
+    }
+    Some(_) => command,
+    None
+      if command
+        .get_arguments()
+        .any(|declared| declared.get_id() == arg) =>
+    {
+      command.mut_arg(arg, update)
+    }
+    None => command,
+  }
+}
+
+/// Encodes a path (`group/project`) to be used as an id in a route.
+pub(crate) fn encode(path: &str) -> String {
+  utf8_percent_encode(path, PATH_SEGMENT).to_string()
+}
+
+/// Encoded path of a project or a group given by its path or its web URL.
+///
+/// `None` when the id is not a path: a numeric id or an already encoded path.
+/// `prefix` is removed from the path of the web URLs (`groups/` for the groups).
+fn namespace_id(id: &str, prefix: &str, session: &Session) -> Result<Option<String>> {
+  Ok(match WebUrl::given(id, session)? {
+    Some(url) => Some(encode(url.path.strip_prefix(prefix).unwrap_or(&url.path))),
+    None if id.contains('/') => Some(encode(id.trim_matches('/'))),
+    None => None,
+  })
+}
+
+/// Project of the git working tree, or else the default project.
+fn current_project(settings: &Config) -> Option<String> {
+  checkout::origin_project()
+    .or_else(|| profiles::default_project(settings))
+    .map(|path| encode(&path))
+}
+
+/// Open merge request whose source branch is the current branch.
+async fn branch_merge_request(session: &Session, project: &str) -> Result<u32> {
+  let branch = checkout::current_branch().ok_or_else(|| {
+    miette!("`.` is the merge request of the current branch, but no branch is checked out.")
+  })?;
+  let merge_requests: Vec<BranchMergeRequest> = session
+    .get(&format!(
+      "/projects/{project}/merge_requests?state=opened&source_branch={}",
+      encode(&branch)
+    ))
+    .await?;
+  merge_requests
+    .first()
+    .map(|merge_request| merge_request.iid)
+    .ok_or_else(|| miette!("No open merge request for the branch `{branch}`."))
+}
+
+/// Makes the argument parse `from` as `to`.
+fn replace_value(arg: Arg, from: String, to: String) -> Arg {
+  arg.value_parser(
+    StringValueParser::new().map(move |value| if value == from { to.clone() } else { value }),
+  )
+}
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/issues",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/issues",
     query_struct = "IssueFilter",
     cli_help = "Get all issues the authenticated user has access to.",
@@ -27,6 +28,7 @@ use parameter `--scope=all`."
   ),
   endpoint(
     route = "/issues/{id}",
+    extra_action = "output_result",
     cli_route = "/issues/{id}",
     cli_help = "Only for administrators. Get a single issue.",
     cli_long_help = "Only for administrators. Get a single issue.
@@ -36,6 +38,7 @@ The preferred way to do this is by using personal access tokens."
 #[api(endpoint(
   route = "/groups/{id}/issues",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/groups/{id}/issues",
   query_struct = "IssueFilter",
   cli_help = "Get a list of a group's issues.",
@@ -47,6 +50,7 @@ The preferred way to do this, is by using personal access tokens."
   endpoint(
     route = "/projects/{id}/issues",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/issues",
     query_struct = "IssueFilter",
     cli_help = "Get a list of a project's issues.",
@@ -56,6 +60,7 @@ The preferred way to do this, is by using personal access tokens."
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}",
     cli_help = "Get a single project issue.",
     cli_long_help = "Get a single project issue.
@@ -64,6 +69,7 @@ The preferred way to do this, is by using personal access tokens."
   ),
   endpoint(
     route = "/projects/{id}/issues",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/create",
     method = "POST",
     result_ok_status = "CREATED",
@@ -72,6 +78,7 @@ The preferred way to do this, is by using personal access tokens."
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/edit",
     method = "PUT",
     payload_struct = "IssueUpdatePayload",
@@ -81,6 +88,7 @@ closed.",
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/delete",
     method = "DELETE",
     result_ok_status = "NO_CONTENT",
@@ -89,6 +97,7 @@ closed.",
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/reorder",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/reorder",
     method = "PUT",
     query_struct = "IssueReorderQuery",
@@ -97,6 +106,7 @@ closed.",
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/move",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/move",
     method = "POST",
     payload_struct = "IssueMovePayload",
@@ -107,6 +117,7 @@ If a given label or milestone with the same name also exists in the target proje
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/subscribe",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/subscribe",
     method = "POST",
     result_ok_status = "CREATED",
@@ -120,6 +131,7 @@ If the user is already subscribed to the issue, the status code 304 is returned.
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/unsubscribe",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/unsubscribe",
     method = "POST",
     result_ok_status = "CREATED",
@@ -134,6 +146,7 @@ If the user is already subscribed to the issue, the status code 304 is returned.
 #[api(endpoint(
   route = "/projects/{id}/merge_requests/{iid}/closes_issues",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/projects/{id}/merge_requests/{iid}/closes_issues",
   cli_help = "Get all the issues that would be closed by merging the provided merge request.",
 ))]
@@ -1,3 +1,5 @@
+pub mod trace;
+
 use crate::{commit::Commit, pipeline::Pipeline, runner::Runner, user::User};
 use crud_api::{Api, ApiInput};
 use serde::{Deserialize, Serialize};
@@ -7,6 +9,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/jobs",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "JobFilter",
     cli_route = "/projects/{id}/jobs",
     cli_help = "Get a list of jobs in a project.",
@@ -15,6 +18,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/pipelines/{pipeline_id}/jobs",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "PipelineJobFilter",
     cli_route = "/projects/{id}/pipelines/{pipeline_id}/jobs",
     cli_help = "Get a list of jobs for a pipeline.",
@@ -22,25 +26,30 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/pipelines/{pipeline_id}/bridges",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "JobFilter",
     cli_route = "/projects/{id}/pipelines/{pipeline_id}/bridges",
     cli_help = "Get a list of bridge jobs for a pipeline.",
   ),
   endpoint(
     route = "/projects/{id}/jobs/{job_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/jobs/{job_id}",
     cli_help = "Get a single job of a project."
   ),
   endpoint(
     route = "/projects/{id}/jobs/{job_id}/trace",
     stream,
+    query_struct = "TraceQuery",
     cli_route = "/projects/{id}/jobs/{job_id}/trace",
-    cli_help = "Get a log (trace) of a specific job of a project."
+    cli_help = "Get a log (trace) of a specific job of a project.",
+    cli_long_help = "Get a log (trace) of a specific job of a project. With `--follow`, the trace is printed while the job runs and the exit code is the final status of the job: 0 for `success`, 1 for `failed`, 2 for `canceled`, 3 for `skipped` and 4 for `manual`."
   ),
   endpoint(
     route = "/projects/{id}/jobs/{job_id}/cancel",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/jobs/{job_id}/cancel",
     cli_help = "Cancel a single job of a project"
   ),
@@ -48,6 +57,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/jobs/{job_id}/retry",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/jobs/{job_id}/retry",
     cli_help = "Retry a single job of a project"
   ),
@@ -55,6 +65,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/jobs/{job_id}/erase",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/jobs/{job_id}/erase",
     cli_help = "Erase a single job of a project (remove job artifacts and a job log)"
   ),
@@ -63,6 +74,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "PlayJobPayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/jobs/{job_id}/play",
     cli_help = "Triggers a manual action to start a job."
   )
@@ -70,17 +82,74 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/job",
   query_struct = "JobTokenQuery",
+  extra_action = "output_result",
   cli_route = "/job",
   cli_help = "Retrieve the job that generated a job token.",
 ))]
 #[api(endpoint(
   route = "/runners/{id}/jobs",
   multiple_results,
+  extra_action = "output_rows",
   query_struct = "JobRunnerFilter",
   cli_route = "/runners/{id}/jobs",
   cli_help = "List jobs that are being processed or were processed by the specified runner.",
   cli_long_help = "List jobs that are being processed or were processed by the specified runner. The list of jobs is limited to projects where the user has at least the Reporter role.",
 ))]
+#[api(
+  endpoint(
+    route = "/projects/{id}/jobs/{job_id}/artifacts",
+    stream,
+    cli_route = "/projects/{id}/jobs/{job_id}/artifacts",
+    cli_help = "Download the artifacts zipped archive of a job.",
+  ),
+  endpoint(
+    route = "/projects/{id}/jobs/{job_id}/artifacts/{artifact_path}",
+    stream,
+    cli_route = "/projects/{id}/jobs/{job_id}/raw/{artifact_path}",
+    cli_help = "Download a single artifact file of a job.",
+    cli_long_help = "Download a single artifact file of a job. `artifact_path` is the path to a file inside the artifacts archive.",
+  ),
+  endpoint(
+    route = "/projects/{id}/jobs/artifacts/{ref_name}/download",
+    stream,
+    query_struct = "ArtifactsJobQuery",
+    cli_route = "/projects/{id}/artifacts/{ref_name}/download",
+    cli_help = "Download the artifacts zipped archive of the latest successful job for a ref.",
+    cli_long_help = "Download the artifacts zipped archive of the latest successful pipeline for the given reference name and job. `ref_name` is a branch or a tag name.",
+  ),
+  endpoint(
+    route = "/projects/{id}/jobs/artifacts/{ref_name}/raw/{artifact_path}",
+    stream,
+    query_struct = "ArtifactsJobQuery",
+    cli_route = "/projects/{id}/artifacts/{ref_name}/raw/{artifact_path}",
+    cli_help = "Download a single artifact file of the latest successful job for a ref.",
+  ),
+  endpoint(
+    route = "/projects/{id}/jobs/{job_id}/artifacts/keep",
+    method = "POST",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/jobs/{job_id}/artifacts/keep",
+    cli_help = "Prevents artifacts from being deleted when expiration is set.",
+  ),
+  endpoint(
+    route = "/projects/{id}/jobs/{job_id}/artifacts",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    cli_no_output,
+    result_struct = "EmptyResponse",
+    cli_route = "/projects/{id}/jobs/{job_id}/artifacts/delete",
+    cli_help = "Delete artifacts of a job.",
+  ),
+  endpoint(
+    route = "/projects/{id}/artifacts",
+    method = "DELETE",
+    result_ok_status = "ACCEPTED",
+    cli_no_output,
+    result_struct = "EmptyResponse",
+    cli_route = "/projects/{id}/artifacts/delete",
+    cli_help = "Delete artifacts of all jobs in a project that can be deleted.",
+  )
+)]
 pub(crate) struct Job {
   id: u32,
   name: String,
@@ -130,6 +199,20 @@ pub(crate) struct Job {
   pipeline: Pipeline,
 }
 
+/// Exit code of the process for a finished job or pipeline.
+///
+/// `None` while the job or the pipeline is not finished.
+pub(crate) fn status_exit_code(status: &str) -> Option<i32> {
+  match status {
+    "success" => Some(0),
+    "failed" => Some(1),
+    "canceled" => Some(2),
+    "skipped" => Some(3),
+    "manual" => Some(4),
+    _ => None,
+  }
+}
+
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
 struct Artifact {
   file_type: String,
@@ -264,6 +347,34 @@ pub(crate) struct JobTokenQuery {
   job_token: String,
 }
 
+/// Options of the trace. They are not sent to Gitlab.
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct TraceQuery {
+  #[api(
+    heading = "Follow",
+    help = "Keep printing the trace until the job is finished.",
+    long_help = "Keep printing the trace until the job is finished, like `tail -f`. The exit code is the final status of the job."
+  )]
+  #[serde(skip)]
+  follow: Option<bool>,
+
+  #[api(
+    no_short,
+    heading = "Follow",
+    help = "Seconds between two polls of the trace (default: 3)."
+  )]
+  #[serde(skip)]
+  interval: Option<u64>,
+}
+
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct ArtifactsJobQuery {
+  #[api(no_short, help = "The name of the job.")]
+  job: String,
+}
+
 #[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct PlayJobPayload {
   #[api(
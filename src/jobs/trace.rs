@@ -0,0 +1,171 @@
+//! `jobs {job_id} trace --follow`: prints the trace of a job while it runs.
+
+use super::{status_exit_code, Job, TraceQuery};
+use crate::session::Session;
+use clap::ArgMatches;
+use crud_api::ApiInput;
+use hyper::{header::RANGE, StatusCode};
+use is_terminal::IsTerminal;
+use miette::{IntoDiagnostic, Result};
+use std::{
+  io::{stdout, Write},
+  time::Duration,
+};
+
+/// Seconds between two polls of the trace.
+const DEFAULT_INTERVAL: u64 = 3;
+
+/// Erases the line. It surrounds the section markers.
+const ERASE_LINE: &str = "\x1b[0K";
+const SECTION_MARKERS: [&str; 2] = ["section_start:", "section_end:"];
+
+pub(crate) fn follow_requested(matches: &ArgMatches) -> bool {
+  TraceQuery::from_clap_matches(matches)
+    .map(|options| options.follow.unwrap_or(false))
+    .unwrap_or(false)
+}
+
+/// Prints the new lines of the trace until the job is finished.
+///
+/// Returns the exit code matching the final status of the job.
+pub(crate) async fn follow(session: &Session, matches: &ArgMatches) -> Result<i32> {
+  let id = session.arg("id").unwrap_or_default();
+  let job_id = session.arg("job_id").unwrap_or_default();
+  let options = TraceQuery::from_clap_matches(matches)?;
+  let interval = Duration::from_secs(options.interval.unwrap_or(DEFAULT_INTERVAL));
+  let mut trace = Trace::new(stdout().is_terminal());
+
+  loop {
+    // The status is read before the trace: when the job is finished, the trace is complete.
+    let job: Job = session
+      .get(&format!("/projects/{id}/jobs/{job_id}"))
+      .await?;
+    let response = session
+      .get_raw(
+        &format!("/projects/{id}/jobs/{job_id}/trace"),
+        &[(RANGE, format!("bytes={}-", trace.offset))],
+      )
+      .await?;
+    let new_bytes = if response.status == StatusCode::PARTIAL_CONTENT {
+      &response.body[..]
+    } else if response.body.len() >= trace.offset {
+      &response.body[trace.offset..]
+    } else {
+      // The trace was erased or restarted.
+      trace.offset = 0;
+      &response.body[..]
+    };
+    trace.push(new_bytes)?;
+
+    if let Some(exit_code) = status_exit_code(&job.status) {
+      trace.flush()?;
+      return Ok(exit_code);
+    }
+    tokio::time::sleep(interval).await;
+  }
+}
+
+/// Part of the trace already received.
+struct Trace {
+  /// Number of bytes of the trace already received.
+  offset: usize,
+  /// Last line, not yet terminated.
+  pending: Vec<u8>,
+  colors: bool,
+}
+
+impl Trace {
+  fn new(colors: bool) -> Self {
+    Trace {
+      offset: 0,
+      pending: vec![],
+      colors,
+    }
+  }
+
+  /// Prints the complete lines of `bytes`.
+  fn push(&mut self, bytes: &[u8]) -> Result<()> {
+    self.offset += bytes.len();
+    self.pending.extend_from_slice(bytes);
+    if let Some(end) = self.pending.iter().rposition(|b| *b == b'\n') {
+      let lines: Vec<u8> = self.pending.drain(..=end).collect();
+      self.print(&lines)?;
+    }
+    Ok(())
+  }
+
+  /// Prints the last line, even if it is not terminated.
+  fn flush(&mut self) -> Result<()> {
+    let mut lines = std::mem::take(&mut self.pending);
+    if !lines.is_empty() {
+      lines.push(b'\n');
+    }
+    self.print(&lines)?;
+    stdout().flush().into_diagnostic()
+  }
+
+  fn print(&self, lines: &[u8]) -> Result<()> {
+    let mut out = stdout().lock();
+    for line in String::from_utf8_lossy(lines).split_inclusive('\n') {
+      let rendered = strip_section_markers(line);
+      let rendered = if self.colors {
+        rendered
+      } else {
+        strip_ansi(&rendered)
+      };
+      // Lines made only of section markers are skipped.
+      if rendered.trim().is_empty() && !line.trim().is_empty() {
+        continue;
+      }
+      out.write_all(rendered.as_bytes()).into_diagnostic()?;
+    }
+    out.flush().into_diagnostic()
+  }
+}
+
+/// Removes the collapsible section markers of Gitlab.
+///
+/// A section starts with `\e[0Ksection_start:<timestamp>:<name>\r\e[0K<header>`
+/// and ends with `\e[0Ksection_end:<timestamp>:<name>\r\e[0K`. Only the header is kept.
+fn strip_section_markers(line: &str) -> String {
+  let mut out = String::with_capacity(line.len());
+  let mut rest = line;
+  while let Some(start) = SECTION_MARKERS
+    .iter()
+    .filter_map(|marker| rest.find(marker))
+    .min()
+  {
+    let before = &rest[..start];
+    out.push_str(before.strip_suffix(ERASE_LINE).unwrap_or(before));
+    rest = match rest[start..].find('\r') {
+      Some(end) => {
+        let after = &rest[start + end + 1..];
+        after.strip_prefix(ERASE_LINE).unwrap_or(after)
+      }
+      None => "",
+    };
+  }
+  out.push_str(rest);
+  out
+}
+
+/// Removes the ANSI escape sequences (colors, erase line...).
+fn strip_ansi(line: &str) -> String {
+  let mut out = String::with_capacity(line.len());
+  let mut chars = line.chars();
+  while let Some(c) = chars.next() {
+    if c == '\x1b' {
+      if chars.next() == Some('[') {
+        // The sequence ends with a byte in the range 0x40–0x7E.
+        for c in chars.by_ref() {
+          if ('\x40'..='\x7e').contains(&c) {
+            break;
+          }
+        }
+      }
+    } else {
+      out.push(c);
+    }
+  }
+  out
+}
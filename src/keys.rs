@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/user/keys",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/user/keys",
     cli_help = "Get a list of currently authenticated user's SSH keys.",
   ),
@@ -15,6 +16,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "SSHKeyCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/user/keys/create",
     cli_help = "Creates a new key owned by the currently authenticated user.",
   ),
@@ -33,6 +35,7 @@ use serde::{Deserialize, Serialize};
     route = "/user/keys/{id}",
     method = "DELETE",
     result_ok_status = "NO_CONTENT",
+    extra_action = "output_result",
     cli_route = "/user/keys/{id}/delete",
     cli_help = "Deletes key owned by currently authenticated user.",
   ),
@@ -49,16 +52,19 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/users/{id}/keys",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/users/{id}/keys",
     cli_help = "Get a list of a specified user's SSH keys.",
   ),
   endpoint(
     route = "/users/{id}/keys/{kid}",
+    extra_action = "output_result",
     cli_route = "/users/{id}/keys/{kid}",
     cli_help = "Get a single key for a given user.",
   ),
   endpoint(
     route = "/user/keys/{id}",
+    extra_action = "output_result",
     cli_route = "/user/keys/{id}",
     cli_help = "Get a single key.",
   )
@@ -67,12 +73,14 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/keys",
     query_struct = "SSHKeyFilter",
+    extra_action = "output_result",
     cli_route = "/keys/by_fingerprint",
     cli_help = "Get user by fingerprint of SSH key.",
     cli_long_help = "Get SSH key with user by ID of an SSH key. You can search for a user that owns a specific SSH key. Note only administrators can lookup SSH key with the fingerprint of an SSH key.",
   ),
   endpoint(
     route = "/keys/{id}",
+    extra_action = "output_result",
     cli_route = "/keys/by_id/{id}",
     cli_force_output_format = true,
     cli_help = "Get SSH key with user by ID of an SSH key.",
@@ -124,11 +132,13 @@ pub(crate) struct SSHKeyFilter {
   endpoint(
     route = "/user/gpg_keys",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/user/gpg_keys",
     cli_help = "Get a list of currently authenticated user's GPG keys.",
   ),
   endpoint(
     route = "/user/gpg_keys/{id}",
+    extra_action = "output_result",
     cli_route = "/user/gpg_keys/{id}",
     cli_help = "Get a specific GPG key of currently authenticated user.",
   ),
@@ -137,6 +147,7 @@ pub(crate) struct SSHKeyFilter {
     method = "POST",
     payload_struct = "GpgKeyCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/user/gpg_keys/create",
     cli_help = "Creates a new GPG key owned by the currently authenticated user.",
   ),
@@ -154,12 +165,14 @@ pub(crate) struct SSHKeyFilter {
   endpoint(
     route = "/users/{id}/gpg_keys",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/users/{id}/gpg_keys",
     cli_long_help = "Get a list of a specified user's GPG keys. This endpoint can be accessed without authentication.",
     cli_help = "Get a list of a specified user's GPG keys.",
   ),
   endpoint(
     route = "/users/{id}/gpg_keys/{key_id}",
+    extra_action = "output_result",
     cli_route = "/users/{id}/gpg_keys/{key_id}",
     cli_help = "Get a specific GPG key for a given user.",
   ),
@@ -168,6 +181,7 @@ pub(crate) struct SSHKeyFilter {
     method = "POST",
     payload_struct = "GpgKeyCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/users/{id}/gpg_keys/create",
     cli_help = "Create new GPG key owned by the specified user.",
   ),
@@ -193,3 +207,143 @@ pub(crate) struct GpgKeyCreatePayload {
   #[api(no_short, help = "The new GPG key")]
   key: String,
 }
+
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/deploy_keys",
+    query_struct = "DeployKeyFilter",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/deploy_keys",
+    cli_help = "Get a list of a project's deploy keys.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_keys/{key_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deploy_keys/{key_id}",
+    cli_help = "Get a single deploy key of a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_keys",
+    method = "POST",
+    payload_struct = "DeployKeyCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deploy_keys/add",
+    cli_help = "Creates a new deploy key for a project.",
+    cli_long_help = "Creates a new deploy key for a project. If the deploy key already exists in another project, it's joined to the current project only if the original one is accessible by the same user.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_keys/{key_id}/enable",
+    method = "POST",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deploy_keys/{key_id}/enable",
+    cli_help = "Enables a deploy key of another project for this project.",
+    cli_long_help = "Enables a deploy key of another project for this project, so it can be used by several projects. The user must have access to the other project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_keys/{key_id}",
+    method = "PUT",
+    payload_struct = "DeployKeyUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/deploy_keys/{key_id}/update",
+    cli_help = "Updates the title or the write access of a deploy key of a project.",
+  ),
+  endpoint(
+    route = "/projects/{id}/deploy_keys/{key_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    cli_no_output,
+    result_struct = "EmptyResponse",
+    cli_route = "/projects/{id}/deploy_keys/{key_id}/delete",
+    cli_help = "Removes a deploy key from the project.",
+    cli_long_help = "Removes a deploy key from the project. If the deploy key is used only for this project, it's deleted from the system.",
+  )
+)]
+pub(crate) struct DeployKey {
+  id: u32,
+  title: String,
+  #[api(table_skip)]
+  key: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  fingerprint: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  fingerprint_sha256: Option<String>,
+  created_at: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  expires_at: Option<String>,
+  /// Write access of the key to the project: not given by the list of all the deploy keys.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  can_push: Option<bool>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
+#[api(no_input_file)]
+pub(crate) struct DeployKeyFilter {
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  per_page: Option<u32>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
+#[api(no_input_file)]
+pub(crate) struct DeployKeyCreatePayload {
+  #[api(no_short, help = "New deploy key's title")]
+  title: String,
+
+  #[api(no_short, help = "New deploy key")]
+  key: String,
+
+  #[api(
+    no_short,
+    long = "can-push",
+    help = "Can the deploy key push to the project's repository."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  can_push: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "expires-at",
+    help = "The expiration date of the deploy key.",
+    long_help = "The expiration date of the deploy key in ISO 8601 format (`YYYY-MM-DDTHH:MM:SSZ`)"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  expires_at: Option<String>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
+#[api(no_input_file)]
+pub(crate) struct DeployKeyUpdatePayload {
+  #[api(no_short, help = "New deploy key's title")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  title: Option<String>,
+
+  /// A string: a flag could not remove the write access. Gitlab parses it as a boolean.
+  #[api(
+    no_short,
+    long = "can-push",
+    help = "Allow (`true`) or forbid (`false`) the deploy key to push to the project's repository.",
+    possible_values = "true,false"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  can_push: Option<String>,
+}
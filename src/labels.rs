@@ -6,12 +6,14 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/labels",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/labels",
     query_struct = "LabelQuery",
     cli_help = "Get all labels for a given project.",
   ),
   endpoint(
     route = "/projects/{id}/labels/{label_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/labels/{label_id}",
     cli_help = "Get a single label for a given project.",
   ),
@@ -19,6 +21,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/labels",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/labels/create",
     payload_struct = "CreateLabelPayload",
     cli_help = "Creates a new label for the given repository with the given name and color.",
@@ -36,6 +39,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/labels/{label_id}",
     method = "PUT",
     payload_struct = "UpdateLabelPayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/labels/{label_id}/edit",
     cli_help = "Updates an existing label with new name or new color.",
     cli_long_help = "Updates an existing label with new name or new color. At least one parameter is required, to update the label.",
@@ -43,6 +47,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/labels/{label_id}/promote",
     method = "PUT",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/labels/{label_id}/promote",
     cli_help = "Promotes a project label to a group label.",
     cli_long_help = "Promotes a project label to a group label. The label keeps its ID.",
@@ -52,6 +57,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     cli_help = "Subscribes the authenticated user to a label to receive notifications.",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/labels/{label_id}/subscribe",
     cli_long_help = "Subscribes the authenticated user to a label to receive notifications. If the user is already subscribed to the label, the status code 304 is returned.",
   ),
@@ -59,6 +65,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/labels/{label_id}/unsubscribe",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/labels/{label_id}/unsubscribe",
     cli_help = "Unsubscribes the authenticated user from a label to not receive notifications from it.",
     cli_long_help = "Unsubscribes the authenticated user from a label to not receive notifications from it. If the user is not subscribed to the label, the status code 304 is returned.",
@@ -68,12 +75,14 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/labels",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/labels",
     query_struct = "GroupLabelQuery",
     cli_help = "Get all labels for a given group.",
   ),
   endpoint(
     route = "/groups/{id}/labels/{label_id}",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/labels/{label_id}",
     cli_help = "Get a single label for a given group.",
   ),
@@ -81,6 +90,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/labels",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/labels/create",
     payload_struct = "CreateLabelPayload",
     cli_help = "Creates a new label for the given repository with the given name and color.",
@@ -98,6 +108,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/labels/{label_id}",
     method = "PUT",
     payload_struct = "UpdateLabelPayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/labels/{label_id}/edit",
     cli_help = "Updates an existing group label.",
     cli_long_help = "Updates an existing group label. At least one parameter is required, to update the group label.",
@@ -105,6 +116,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/groups/{id}/labels/{label_id}/promote",
     method = "PUT",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/labels/{label_id}/promote",
     cli_help = "Promotes a group label to a group label.",
     cli_long_help = "Promotes a group label to a group label. The label keeps its ID.",
@@ -114,6 +126,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     cli_help = "Subscribes the authenticated user to a label to receive notifications.",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/labels/{label_id}/subscribe",
     cli_long_help = "Subscribes the authenticated user to a label to receive notifications. If the user is already subscribed to the label, the status code 304 is returned.",
   ),
@@ -121,6 +134,7 @@ use serde::{Deserialize, Serialize};
     route = "/groups/{id}/labels/{label_id}/unsubscribe",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/labels/{label_id}/unsubscribe",
     cli_help = "Unsubscribes the authenticated user from a label to not receive notifications from it.",
     cli_long_help = "Unsubscribes the authenticated user from a label to not receive notifications from it. If the user is not subscribed to the label, the status code 304 is returned.",
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
     route = "/ci/lint",
     method = "POST",
     payload_struct = "LintPayload",
+    extra_action = "output_result",
     cli_route = "/ci/lint",
     cli_help = "Checks if CI/CD YAML configuration is valid.",
     cli_long_help = "Checks if CI/CD YAML configuration is valid. This endpoint validates basic CI/CD configuration syntax.",
@@ -15,6 +16,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/ci/lint",
     method = "POST",
     payload_struct = "LintProjectPayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/ci/lint",
     cli_help = "Checks if CI/CD YAML configuration is valid.",
     cli_long_help = "Checks if CI/CD YAML configuration is valid. This endpoint has namespace specific context.",
@@ -22,6 +24,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/ci/lint",
     payload_struct = "LintCurrentProjectPayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/ci/lint_project",
     cli_help = "Checks if a project's latest (HEAD of the project's default branch) .gitlab-ci.yml configuration is valid.",
     cli_long_help = "Checks if a project's latest (HEAD of the project's default branch) .gitlab-ci.yml configuration is valid. This endpoint uses all namespace specific data available, including variables, local includes, and so on.",
@@ -1,13 +1,20 @@
+mod approvals;
 mod badges;
 mod branches;
+mod checkout;
+mod commands;
 mod commit;
+mod deployments;
 mod diff;
+mod discussions;
 mod emails;
+mod environments;
 mod events;
 mod export;
 mod features;
 mod groups;
 mod hooks;
+mod ids;
 mod issues;
 mod jobs;
 mod keys;
@@ -16,14 +23,24 @@ mod lint;
 mod markdown;
 mod merge_requests;
 mod milestones;
+mod notes;
+mod output;
+mod pagination;
 mod pipeline;
+mod profiles;
 mod projects;
+mod protected;
 mod reference;
+mod relay;
+mod releases;
 mod repository;
+mod retry;
 mod runner;
+mod session;
 mod star;
 mod tag;
 mod task;
+mod template;
 mod time;
 mod todo;
 mod user;
@@ -32,12 +49,31 @@ mod version;
 mod access_token; // should be listed after "user".
 
 use crate::{
-  access_token::{AccessToken, AccessTokenCreatePayload},
+  access_token::{
+    AccessToken, AccessTokenCreatePayload, DeployToken, DeployTokenCreatePayload, DeployTokenFilter,
+  },
+  approvals::{
+    ApprovalRule, ApprovalRuleCreatePayload, ApprovalRuleFilter, ApprovalRuleUpdatePayload,
+    ApprovalState, ApprovePayload, MergeRequestApprovalRuleCreatePayload,
+    MergeRequestApprovalRuleUpdatePayload, MergeRequestApprovals, ProjectApprovals,
+    ProjectApprovalsPayload,
+  },
   badges::{Badge, BadgeCreatePayload, BadgeQuery},
-  branches::{Branch, BranchCreatePayload, BranchQuery},
-  commit::{Commit, MergeBaseQuery},
-  diff::DiffVersion,
+  branches::{output_branches, Branch, BranchCreatePayload, BranchQuery},
+  commit::{
+    CherryPickPayload, Commit, CommitFilter, CommitRef, CommitRefFilter, CommitStatus,
+    CommitStatusCreatePayload, CommitStatusFilter, MergeBaseQuery, RevertPayload,
+  },
+  deployments::{Deployment, DeploymentApproval, DeploymentApprovalPayload, DeploymentFilter},
+  diff::{Change, DiffVersion},
+  discussions::{
+    Conversation, Discussion, DiscussionCreatePayload, DiscussionFilter, DiscussionResolveQuery,
+  },
   emails::{Email, EmailCreatePayload, EmailCreateWithValidationPayload},
+  environments::{
+    Environment, EnvironmentCreatePayload, EnvironmentFilter, EnvironmentStopPayload,
+    EnvironmentUpdatePayload,
+  },
   events::{Event, EventFilter},
   export::{Export, ExportPayload, ExportRelation, ExportRelationQuery},
   features::{Definition, Feature},
@@ -53,36 +89,59 @@ use crate::{
     edit::IssueUpdatePayload,
     Issue, IssueFilter,
   },
-  jobs::{Job, JobFilter, JobRunnerFilter, JobTokenQuery, PipelineJobFilter, PlayJobPayload},
-  keys::{GpGKey, GpgKeyCreatePayload, SSHKey, SSHKeyCreatePayload, SSHKeyFilter},
+  jobs::{
+    ArtifactsJobQuery, Job, JobFilter, JobRunnerFilter, JobTokenQuery, PipelineJobFilter,
+    PlayJobPayload, TraceQuery,
+  },
+  keys::{
+    DeployKey, DeployKeyCreatePayload, DeployKeyFilter, DeployKeyUpdatePayload, GpGKey,
+    GpgKeyCreatePayload, SSHKey, SSHKeyCreatePayload, SSHKeyFilter,
+  },
   labels::{CreateLabelPayload, GroupLabelQuery, Label, LabelQuery, UpdateLabelPayload},
   lint::{Lint, LintCurrentProjectPayload, LintPayload, LintProjectPayload},
   markdown::{Markdown, MarkdownQuery},
   merge_requests::{
-    CommidId, MergeRequest, MergeRequestChangeSelector, MergeRequestCreate, MergeRequestFilter,
-    MergeRequestMerge, MergeRequestSelector, MergeRequestUpdate,
+    output_merge_request, output_merge_requests, CommidId, MergeRequest, MergeRequestChangeSelector,
+    MergeRequestCreate, MergeRequestFilter, MergeRequestMerge, MergeRequestSelector,
+    MergeRequestUpdate,
+  },
+  notes::{DiscussionNotePayload, Note, NoteCreatePayload, NoteFilter, NoteUpdatePayload},
+  output::{output_result, output_rows},
+  pipeline::{
+    watch_pipeline, Pipeline, PipelineCreate, PipelineFilter, PipelineSchedule,
+    PipelineScheduleCreatePayload, PipelineScheduleEditPayload, PipelineScheduleFilter,
+    PipelineScheduleVariable, PipelineScheduleVariableCreatePayload,
+    PipelineScheduleVariableEditPayload, TestReport, TestReportSummary, Variables, WatchQuery,
   },
-  pipeline::{Pipeline, PipelineCreate, PipelineFilter, TestReport, TestReportSummary, Variables},
   projects::{
     actions::{
       ProjectSharePayload, ProjectsForkQuery, ProjectsForksQuery, ProjectsSnapshot, TransferQuery,
     },
     create::ProjectsCreatePayload,
     variables::{
-      ProjectVariables, ProjectsVariableCreatePayload, ProjectsVariableDeletePayload,
+      transfer, ProjectVariables, ProjectsVariableCreatePayload, ProjectsVariableDeletePayload,
       ProjectsVariableUpdatePayload,
     },
     Project, ProjectDetailFilter, ProjectsFilter, UsersProjectsFilter,
   },
+  protected::{
+    ProtectedBranch, ProtectedBranchCreatePayload, ProtectedBranchQuery,
+    ProtectedBranchUpdatePayload, ProtectedTag, ProtectedTagCreatePayload, ProtectedTagQuery,
+  },
+  releases::{
+    Release, ReleaseCreatePayload, ReleaseFilter, ReleaseLink, ReleaseLinkCreatePayload,
+    ReleaseLinkFilter, ReleaseLinkUpdatePayload, ReleaseUpdatePayload,
+  },
   repository::{
     blob::Blob,
     changelog::{Changelog, ChangelogPayload},
     compare::{Compare, CompareQuery},
     contributor::{Contributor, ContributorSort},
     file::{blame::Blame, File, FileCreatePayload, FileDeletePayload, FilePayload},
-    tree::{Tree, TreeFilter},
+    tree::{download, ArchiveQuery, Tree, TreeFilter},
   },
-  runner::{Runner, RunnerFilter, RunnerId, RunnerTokenPayload, RunnerUpdate},
+  runner::{Runner, RunnerCreatePayload, RunnerFilter, RunnerId, RunnerTokenPayload, RunnerUpdate},
+  session::{Auth, Session},
   star::Starrers,
   tag::{CreateTagPayload, Tag, TagQuery},
   time::{IssueAddSpentTimeQuery, IssueTimeEstimateQuery, TimeStats},
@@ -100,10 +159,12 @@ use crate::{
   },
   version::Version,
 };
+use clap::ArgMatches;
+use commands::Custom;
 use crud_api::{Api, ApiInput, ApiRun, EmptyResponse, Query};
 use crud_auth::CrudAuth;
-use crud_auth_bearer::Auth;
 use miette::Result;
+use std::process::ExitCode;
 
 #[derive(ApiRun)]
 #[api(infos(
@@ -118,6 +179,32 @@ use miette::Result;
 struct Gitlab {}
 
 #[tokio::main]
-async fn main() -> Result<()> {
-  Gitlab::run().await
+async fn main() -> Result<ExitCode> {
+  ids::resolve().await?;
+  if let Some((command, matches)) = commands::parse() {
+    return run_custom(command, &matches).await;
+  }
+  relay::start().await?;
+  output::exit_code(Gitlab::run().await)
+}
+
+/// Runs a command that is not one call to an endpoint, see [commands].
+async fn run_custom(command: Custom, matches: &ArgMatches) -> Result<ExitCode> {
+  pretty_env_logger::init();
+  let settings = profiles::settings()?;
+  let session = Session::init(matches, &settings);
+  let (_, leaf) = session.subcommands();
+  exit_code(match command {
+    Custom::Config => profiles::run_config(matches, &settings).map(|()| 0),
+    Custom::ExportVariables => transfer::export(session, leaf).await,
+    Custom::ImportVariables => transfer::import(session, leaf).await,
+    Custom::CreateCommit => commit::create::run(session, leaf).await,
+    Custom::DownloadTree => download::run(leaf).await,
+    Custom::FollowTrace => jobs::trace::follow(session, leaf).await,
+    Custom::CreateReleaseFromChangelog => releases::create_from_changelog(session, leaf).await,
+  })
+}
+
+fn exit_code(result: Result<i32>) -> Result<ExitCode> {
+  result.map(|code| ExitCode::from(code as u8))
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
   method = "POST",
   result_ok_status = "CREATED",
   payload_struct = "MarkdownQuery",
+  extra_action = "output_result",
   cli_route = "/markdown",
   cli_help = "Render an arbitrary Markdown document",
 ))]
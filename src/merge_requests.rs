@@ -1,15 +1,31 @@
 use crate::{
-  diff::Change, milestones::Milestone, pipeline::Pipeline, reference::References,
-  task::TaskCompletionStatus, time::TimeStats, user::User,
+  approvals::{ApprovalSummary, MergeRequestApprovals},
+  diff::Change,
+  milestones::Milestone,
+  output::{output_joined_result, output_joined_rows, output_rows},
+  pagination,
+  pipeline::Pipeline,
+  reference::References,
+  session::Session,
+  task::TaskCompletionStatus,
+  time::TimeStats,
+  user::User,
 };
+use config::Config;
 use crud_api::{Api, ApiInput};
+use miette::Result;
 use serde::{Deserialize, Serialize};
 
+/// Default columns of the merge requests joined with their approvals by `--approvals`.
+const APPROVALS_COLUMNS: &str =
+  "iid,title,state,approval.approved,approval.approved_by,approval.approvals_left,approval.rules_left";
+
 #[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
 #[api(
   endpoint(
     route = "/merge_requests",
     multiple_results,
+    extra_action = "output_merge_requests",
     query_struct = "MergeRequestFilter",
     cli_route = "/merge_requests",
     cli_help = "Get all merge requests the authenticated user has access to.",
@@ -18,15 +34,24 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/merge_requests",
     multiple_results,
+    extra_action = "output_merge_requests",
     query_struct = "MergeRequestFilter",
     cli_route = "/projects/{id}/merge_requests",
     cli_help = "Get all merge requests for this project.",
   ),
+  endpoint(
+    route = "/projects/{id}/repository/commits/{sha}/merge_requests",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/repository/commits/{sha}/merge_requests",
+    cli_help = "Get a list of merge requests related to the specified commit.",
+  ),
   endpoint(
     route = "/projects/{id}/merge_requests",
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "MergeRequestCreate",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/create",
     cli_help = "Creates a new merge request.",
   ),
@@ -34,6 +59,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/merge_requests/{iid}",
     method = "PUT",
     payload_struct = "MergeRequestUpdate",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/edit",
     cli_help = "Updates an existing merge request.",
     cli_long_help = "Updates an existing merge request. You can change the target branch, title, or even close the MR.",
@@ -41,19 +67,23 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}",
     method = "DELETE",
-    cli_route = "/projects/{id}/merge_requests//{iid}/delete",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/delete",
     cli_help = "Deletes the merge request in question.",
     cli_long_help = "Only for administrators and project owners. Deletes the merge request in question.",
   ),
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}",
     query_struct = "MergeRequestSelector",
+    extra_action = "output_merge_request",
     cli_route = "/projects/{id}/merge_requests/{iid}",
     cli_help = "Shows information about a single merge request.",
+    cli_long_help = "Shows information about a single merge request, including who approved it and the approval rules still waiting for approvals.",
   ),
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/changes",
     query_struct = "MergeRequestChangeSelector",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/changes",
     cli_help = "Shows information about the merge request including its files and changes.",
   ),
@@ -61,7 +91,8 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/merge_requests/{iid}/merge",
     method = "PUT",
     payload_struct = "MergeRequestMerge",
-    cli_route = "/projects/{id}/merge_requests//{iid}/merge",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/merge",
     cli_help = "Merge changes submitted with MR using this API.",
     cli_long_help = "Merge changes submitted with MR using this API.
 If a merge request is unable to be accepted (such as Draft, Closed, Pipeline Pending Completion, or Failed while requiring Success) - you receive a 405 and the error message 'Method Not Allowed'
@@ -72,7 +103,8 @@ If you don't have permissions to accept this merge request - you receive a 401",
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/cancel_merge_when_pipeline_succeeds",
     method = "POST",
-    cli_route = "/projects/{id}/merge_requests//{iid}/cancel_merge_when_pipeline_succeeds",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/cancel_merge_when_pipeline_succeeds",
     cli_help = "Cancel Merge When Pipeline Succeeds",
   ),
   endpoint(
@@ -80,7 +112,8 @@ If you don't have permissions to accept this merge request - you receive a 401",
     method = "POST",
     result_ok_status = "CREATED",
     result_ko_status(status = "NOT_MODIFIED", message = "Already subscribed"),
-    cli_route = "/projects/{id}/merge_requests//{iid}/subscribe",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/subscribe",
     cli_help = "Subscribes the authenticated user to a merge request to receive notification.",
   ),
   endpoint(
@@ -88,24 +121,19 @@ If you don't have permissions to accept this merge request - you receive a 401",
     method = "POST",
     result_ok_status = "CREATED",
     result_ko_status(status = "NOT_MODIFIED", message = "already unsubscribed"),
-    cli_route = "/projects/{id}/merge_requests//{iid}/unsubscribe",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/unsubscribe",
     cli_help = "Unsubscribes the authenticated user from a merge request to not receive notifications from that merge request.",
   ),
   endpoint(
     route = "/groups/{id}/merge_requests",
     multiple_results,
+    extra_action = "output_merge_requests",
     query_struct = "MergeRequestFilter",
     cli_route = "/groups/{id}/merge_requests",
     cli_help = "Get all merge requests for this group and its subgroups.",
   )
 )]
-#[api(endpoint(
-  route = "/groups/{id}/merge_requests",
-  multiple_results,
-  query_struct = "MergeRequestFilter",
-  cli_route = "/groups/{id}/merge_requests",
-  cli_help = "Get all merge requests for this group and its subgroups.",
-))]
 pub(crate) struct MergeRequest {
   id: u32,
   iid: u32,
@@ -277,6 +305,54 @@ pub(crate) struct MergeRequest {
   #[api(table_skip)]
   #[serde(skip_serializing_if = "Vec::is_empty")]
   reviewers: Vec<User>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  approvals: Option<MergeRequestApprovals>,
+  /// Summary of the approvals, joined by `--approvals`.
+  #[api(table_skip)]
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  approval: Option<ApprovalSummary>,
+}
+
+/// `extra_action` of the merge request: it is printed with its approvals.
+pub(crate) fn output_merge_request(merge_request: &MergeRequest, settings: &Config) -> Result<()> {
+  let session = Session::current()?;
+  let merge_request = MergeRequest {
+    approvals: Some(approvals(session, merge_request)?),
+    ..merge_request.clone()
+  };
+  output_joined_result(&merge_request, settings)
+}
+
+/// `extra_action` of the lists of merge requests: with `--approvals`, the merge requests are
+/// printed with a summary of their approvals.
+pub(crate) fn output_merge_requests(
+  merge_requests: &[MergeRequest],
+  settings: &Config,
+) -> Result<()> {
+  let session = Session::current()?;
+  let filter = MergeRequestFilter::from_clap_matches(session.subcommands().1)?;
+  // The next pages of `--all` are printed as received.
+  if !filter.approvals.unwrap_or_default() || pagination::requested(session.subcommands().1) {
+    return output_rows(merge_requests, settings);
+  }
+  let merge_requests: Vec<MergeRequest> = merge_requests
+    .iter()
+    .map(|merge_request| {
+      Ok(MergeRequest {
+        approval: Some(ApprovalSummary::from(&approvals(session, merge_request)?)),
+        ..merge_request.clone()
+      })
+    })
+    .collect::<Result<_>>()?;
+  output_joined_rows(&merge_requests, settings, APPROVALS_COLUMNS)
+}
+
+fn approvals(session: &Session, merge_request: &MergeRequest) -> Result<MergeRequestApprovals> {
+  session.blocking_get(&format!(
+    "/projects/{}/merge_requests/{}/approvals",
+    merge_request.project_id, merge_request.iid
+  ))
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -618,6 +694,14 @@ pub(crate) struct MergeRequestFilter {
     long_help = "Number of items to list per page (default: 20, max: 100)."
   )]
   per_page: Option<u32>,
+
+  #[api(
+    no_short,
+    help = "Show the approvals of the merge requests.",
+    long_help = "Show the approvals of the merge requests: whether they are approved, who approved them, the number of approvals and the approval rules still required."
+  )]
+  #[serde(skip)]
+  approvals: Option<bool>,
 }
 
 #[derive(Debug, ApiInput, Serialize, Deserialize)]
@@ -911,7 +995,8 @@ pub(crate) struct MergeRequestMerge {
 #[derive(Api, Serialize, Deserialize, Debug, Default)]
 #[api(endpoint(
   route = "/projects/{id}/merge_requests/{iid}/merge_ref",
-  cli_route = "/projects/{id}/merge_requests/{iid}//merge_ref",
+  extra_action = "output_result",
+  cli_route = "/projects/{id}/merge_requests/{iid}/merge_ref",
   cli_help = "Merge the changes between the merge request source and target branches into refs/merge-requests/:iid/merge ref, of the target project repository, if possible.",
 ))]
 pub(crate) struct CommidId {
@@ -0,0 +1,331 @@
+use crate::user::User;
+use crud_api::{Api, ApiInput};
+use crud_pretty_struct::{
+  formatters::{bool_check_formatter, markdown_formatter},
+  PrettyPrint,
+};
+use miette::{IntoDiagnostic, WrapErr};
+use serde::{Deserialize, Serialize};
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/notes",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "NoteFilter",
+    cli_route = "/projects/{id}/merge_requests/{iid}/notes",
+    cli_help = "Get a list of all notes for a single merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/notes/{note_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/notes/{note_id}",
+    cli_help = "Get a single note for a given merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/notes",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "NoteCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/notes/create",
+    cli_help = "Creates a new note for a single merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/notes/{note_id}",
+    method = "PUT",
+    payload_struct = "NoteUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/notes/{note_id}/edit",
+    cli_help = "Modify existing note of a merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/notes/{note_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/merge_requests/{iid}/notes/{note_id}/delete",
+    cli_help = "Deletes an existing note of a merge request.",
+  )
+)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/notes",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "DiscussionNotePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/reply",
+    cli_help = "Adds a new note to the thread.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/notes/{note_id}",
+    method = "PUT",
+    payload_struct = "NoteUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/notes/{note_id}/edit",
+    cli_help = "Modify existing thread note of a merge request.",
+  ),
+  endpoint(
+    route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/notes/{note_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/notes/{note_id}/delete",
+    cli_help = "Deletes an existing thread note of a merge request.",
+  )
+)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/notes",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "NoteFilter",
+    cli_route = "/projects/{id}/issues/{iid}/notes",
+    cli_help = "Get a list of all notes for a single issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/notes/{note_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/notes/{note_id}",
+    cli_help = "Get a single note for a given issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/notes",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "NoteCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/notes/create",
+    cli_help = "Creates a new note for a single issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/notes/{note_id}",
+    method = "PUT",
+    payload_struct = "NoteUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/notes/{note_id}/edit",
+    cli_help = "Modify existing note of an issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/notes/{note_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/issues/{iid}/notes/{note_id}/delete",
+    cli_help = "Deletes an existing note of an issue.",
+  )
+)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}/notes",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "DiscussionNotePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}/reply",
+    cli_help = "Adds a new note to the thread.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}/notes/{note_id}",
+    method = "PUT",
+    payload_struct = "NoteUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}/notes/{note_id}/edit",
+    cli_help = "Modify existing thread note of an issue.",
+  ),
+  endpoint(
+    route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}/notes/{note_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/issues/{iid}/discussions/{discussion_id}/notes/{note_id}/delete",
+    cli_help = "Deletes an existing thread note of an issue.",
+  )
+)]
+#[derive(PrettyPrint)]
+pub(crate) struct Note {
+  #[pretty(skip)]
+  id: u32,
+  #[api(table_skip)]
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  type_: Option<String>,
+  #[pretty(formatter=markdown_formatter)]
+  body: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none)]
+  attachment: Option<String>,
+  author: User,
+  #[api(table_format(date(format = "%Y-%m-%d %H:%M:%S")))]
+  created_at: String,
+  #[api(table_skip)]
+  #[pretty(skip)]
+  updated_at: String,
+  #[api(table_skip)]
+  #[pretty(skip)]
+  system: bool,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  noteable_id: Option<u32>,
+  #[api(table_skip)]
+  #[pretty(skip)]
+  noteable_type: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  noteable_iid: Option<u32>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  project_id: Option<u32>,
+  #[api(table_skip)]
+  #[pretty(skip)]
+  resolvable: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none, formatter=bool_check_formatter)]
+  resolved: Option<bool>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none)]
+  resolved_by: Option<User>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  resolved_at: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  confidential: Option<bool>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  internal: Option<bool>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip)]
+  position: Option<Position>,
+}
+
+/// Location of a diff note.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct Position {
+  base_sha: String,
+  start_sha: String,
+  head_sha: String,
+  position_type: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  old_path: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  new_path: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  old_line: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  new_line: Option<u32>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct NoteFilter {
+  #[api(
+    no_short,
+    heading = "Sorting",
+    help = "Return notes sorted in `asc` or `desc` order.",
+    long_help = "Return notes sorted in `asc` or `desc` order. Default is `desc`.",
+    possible_values = "asc,desc"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sort: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Sorting",
+    long = "order-by",
+    help = "Return notes ordered by `created_at` or `updated_at` fields.",
+    long_help = "Return notes ordered by `created_at` or `updated_at` fields. Default is `created_at`.",
+    possible_values = "created_at,updated_at"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  order_by: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct NoteCreatePayload {
+  #[api(
+    no_short,
+    help = "The content of a note.",
+    long_help = "The content of a note. Limited to 1,000,000 characters."
+  )]
+  body: String,
+
+  #[api(
+    no_short,
+    help = "The confidential flag of a note.",
+    long_help = "The confidential flag of a note. Default is false."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  internal: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "created-at",
+    help = "Date time string, ISO 8601 formatted.",
+    long_help = "Date time string, ISO 8601 formatted. Example: 2016-03-11T03:45:40Z (requires administrator or project/group owner rights)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  created_at: Option<String>,
+
+  #[api(
+    no_short,
+    long = "merge-request-diff-head-sha",
+    help = "The SHA of the head commit.",
+    long_help = "The SHA of the head commit, which ensures the merge request wasn't updated after the API request was sent. Required for the /merge quick action."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  merge_request_diff_head_sha: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct DiscussionNotePayload {
+  #[api(no_short, help = "The content of the note or reply.")]
+  body: String,
+
+  #[api(
+    no_short,
+    long = "created-at",
+    help = "Date time string, ISO 8601 formatted.",
+    long_help = "Date time string, ISO 8601 formatted. Example: 2016-03-11T03:45:40Z (requires administrator or project/group owner rights)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  created_at: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct NoteUpdatePayload {
+  #[api(no_short, help = "The content of the note.")]
+  body: String,
+}
@@ -0,0 +1,417 @@
+//! Outputs of the lists printed by the CLI instead of crud-api.
+//!
+//! - `csv` and `ndjson` formats. They are unknown to crud-api: the `output_format`
+//!   arguments accept them and parse them as `json`.
+//! - `--columns`: the columns of the table and of the CSV. Any field can be chosen,
+//!   including the nested ones (`author.username`). The default columns of a resource
+//!   are read from the `columns` table of the settings.
+//! - `--sort-by`: sorts the list by a field.
+//! - `--output-template`: renders each result with a template, see [crate::template].
+//!
+//! The lists print them in their `extra_action`, [output_rows]. The other results are
+//! printed by crud-api, as JSON for the new formats, or by [output_result] with a template.
+//! An `extra_action` that printed the result returns [Printed]: crud-api would print it again.
+
+use crate::{pagination, profiles, session::Session, template};
+use clap::{
+  builder::{PossibleValuesParser, TypedValueParser},
+  Arg, ArgAction, ArgMatches, Command,
+};
+use config::Config;
+use crud_api::Api;
+use crud_tidy_viewer::{display_table, TableConfig};
+use miette::{IntoDiagnostic, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+  cmp::Ordering,
+  fmt::{self, Debug, Display, Formatter},
+  io::{stdout, Write},
+  process::ExitCode,
+};
+use toml_edit::{value, Item, Table};
+
+const CSV: &str = "csv";
+const NDJSON: &str = "ndjson";
+const JSON: &str = "json";
+const YAML: &str = "yaml";
+const COLUMNS_SETTING: &str = "columns";
+/// Arguments of the lists declared here. They are not sent in the queries.
+pub(crate) const ARGS: [&str; 5] = [
+  "columns",
+  "save_columns",
+  "sort_by",
+  "reverse",
+  "output_template",
+];
+
+/// Error of the `extra_action`s that printed the result themselves, with the exit code of
+/// the process: crud-api prints the result when the `extra_action` succeeds.
+#[derive(Debug)]
+pub(crate) struct Printed(pub(crate) u8);
+
+impl Display for Printed {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "The result is printed.")
+  }
+}
+
+impl std::error::Error for Printed {}
+
+impl miette::Diagnostic for Printed {}
+
+/// Exit code of the CLI: [Printed] is not an error.
+pub(crate) fn exit_code(result: Result<()>) -> Result<ExitCode> {
+  match result {
+    Ok(()) => Ok(ExitCode::SUCCESS),
+    Err(err) => match err.downcast_ref::<Printed>() {
+      Some(Printed(code)) => Ok(ExitCode::from(*code)),
+      None => Err(err),
+    },
+  }
+}
+
+/// Adds `csv` and `ndjson` to the formats accepted by the `output_format` arguments,
+/// and declares the options of the outputs next to them.
+pub(crate) fn declare(command: Command) -> Command {
+  let output_format = command
+    .get_arguments()
+    .find(|arg| arg.get_id() == "output_format")
+    .map(|arg| {
+      let formats: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .map(|format| format.get_name().to_string())
+        .collect();
+      (formats, arg.get_help_heading().map(str::to_string))
+    });
+  let command = match output_format {
+    Some((mut formats, heading)) => {
+      formats.extend([CSV.to_string(), NDJSON.to_string()]);
+      command
+        .mut_arg("output_format", |arg| {
+          arg.value_parser(PossibleValuesParser::new(formats).map(|format| {
+            if format == CSV || format == NDJSON {
+              JSON.to_string()
+            } else {
+              format
+            }
+          }))
+        })
+        .args(
+          [
+            Arg::new("columns")
+              .long("columns")
+              .value_name("FIELDS")
+              .help("Columns of the table and of the CSV: `iid,title,author.username`."),
+            Arg::new("save_columns")
+              .long("save-columns")
+              .action(ArgAction::SetTrue)
+              .requires("columns")
+              .help("Save the columns as the default columns of the resource."),
+            Arg::new("sort_by")
+              .long("sort-by")
+              .value_name("FIELD")
+              .help("Sort the list by a field: `created_at`, `author.username`..."),
+            Arg::new("reverse")
+              .long("reverse")
+              .action(ArgAction::SetTrue)
+              .requires("sort_by")
+              .help("Sort in descending order."),
+            Arg::new("output_template")
+              .long("output-template")
+              .value_name("TEMPLATE")
+              .help("Render each result with a template, or with the template file `@path`.")
+              .long_help("Render each result with a template, or with the template file `@path`: `{{ iid }} {{ title | truncate(40) }}`. The filters `date`, `ago`, `truncate`, `color` and the builtin filters of MiniJinja (`join`...) are available. The output format is ignored."),
+          ]
+          .map(|arg| match &heading {
+            Some(heading) => arg.help_heading(heading.clone()),
+            None => arg,
+          }),
+        )
+    }
+    None => command,
+  };
+
+  let names: Vec<String> = command
+    .get_subcommands()
+    .map(|subcommand| subcommand.get_name().to_string())
+    .collect();
+  names.iter().fold(command, |command, name| {
+    command.mut_subcommand(name, declare)
+  })
+}
+
+/// `extra_action` of the lists.
+///
+/// When the list is printed here, [Printed] is returned. Otherwise it is printed by crud-api.
+/// With `--all` or `--max-items`, the items of all the pages are printed by
+/// [pagination::print_all].
+pub(crate) fn output_rows<T>(results: &[T], settings: &Config) -> Result<()>
+where
+  T: Api + Serialize + Default,
+{
+  print_rows(results, settings, None)
+}
+
+/// Prints the list completed by its `extra_action`, whatever the options: crud-api would
+/// print the results as they were received.
+///
+/// `default_columns` are the columns of the table when `--columns` is not given.
+pub(crate) fn output_joined_rows<T>(
+  results: &[T],
+  settings: &Config,
+  default_columns: &str,
+) -> Result<()>
+where
+  T: Api + Serialize + Default,
+{
+  print_rows(results, settings, Some(default_columns))
+}
+
+fn print_rows<T>(results: &[T], settings: &Config, default_columns: Option<&str>) -> Result<()>
+where
+  T: Api + Serialize + Default,
+{
+  let matches = Session::current()?.subcommands().1;
+  if pagination::requested(matches) {
+    pagination::print_all(results)?;
+    return Err(Printed(0).into());
+  }
+  let format = raw_value(matches, "output_format");
+  let resource = resource_name::<T>();
+  let columns = match raw_value(matches, "columns") {
+    Some(columns) => {
+      if matches.get_flag("save_columns") {
+        save_columns(settings, &resource, columns)?;
+      }
+      Some(columns.to_string())
+    }
+    None => default_columns.map(str::to_string).or_else(|| {
+      settings
+        .get_string(&format!("{COLUMNS_SETTING}.{resource}"))
+        .ok()
+    }),
+  };
+  let columns: Option<Vec<String>> =
+    columns.map(|columns| columns.split(',').map(|c| c.trim().to_string()).collect());
+  let sort_by = raw_value(matches, "sort_by");
+  let output_template = raw_value(matches, "output_template");
+
+  let printed_here = output_template.is_some()
+    || default_columns.is_some()
+    || match format {
+      None => columns.is_some() || sort_by.is_some(),
+      Some(CSV | NDJSON) => true,
+      Some(JSON | YAML) => sort_by.is_some(),
+      Some(_) => false,
+    };
+  if !printed_here {
+    return Ok(());
+  }
+
+  let mut rows: Vec<(Value, &T)> = results
+    .iter()
+    .map(|result| Ok((serde_json::to_value(result).into_diagnostic()?, result)))
+    .collect::<Result<_>>()?;
+  if let Some(field) = sort_by {
+    rows.sort_by(|(a, _), (b, _)| compare(lookup(a, field), lookup(b, field)));
+    if matches.get_flag("reverse") {
+      rows.reverse();
+    }
+  }
+  if let Some(output_template) = output_template {
+    let rows: Vec<&Value> = rows.iter().map(|(row, _)| row).collect();
+    template::render(&template::source(output_template)?, &rows)?;
+    return Err(Printed(0).into());
+  }
+
+  let table = || -> Result<Vec<Vec<String>>> {
+    match &columns {
+      Some(columns) => Ok(
+        std::iter::once(columns.clone())
+          .chain(rows.iter().map(|(row, _)| {
+            columns
+              .iter()
+              .map(|column| cell(lookup(row, column)))
+              .collect()
+          }))
+          .collect(),
+      ),
+      // The columns of the table: the fields with `table_skip` are skipped.
+      None => std::iter::once(Ok(T::default().to_table_header()))
+        .chain(rows.iter().map(|(_, result)| result.to_table()))
+        .collect(),
+    }
+  };
+
+  let mut out = stdout().lock();
+  match format {
+    None => {
+      if !rows.is_empty() {
+        // The lines of a cell would break the rows of the table.
+        let table: Vec<Vec<String>> = table()?
+          .into_iter()
+          .map(|row| row.iter().map(|cell| cell.replace('\n', "\\n")).collect())
+          .collect();
+        display_table(&table, TableConfig::default());
+      }
+    }
+    Some(CSV) => {
+      for (index, record) in table()?.iter().enumerate() {
+        let record: Vec<String> = if index == 0 {
+          // `_ref` is the field of `ref`.
+          record
+            .iter()
+            .map(|name| name.trim_start_matches('_').to_string())
+            .collect()
+        } else {
+          record.clone()
+        };
+        write_csv_record(&mut out, &record)?;
+      }
+    }
+    Some(NDJSON) => {
+      for (row, _) in &rows {
+        writeln!(out, "{row}").into_diagnostic()?;
+      }
+    }
+    Some(JSON) => {
+      let rows: Vec<&Value> = rows.iter().map(|(row, _)| row).collect();
+      writeln!(
+        out,
+        "{}",
+        serde_json::to_string_pretty(&rows).into_diagnostic()?
+      )
+      .into_diagnostic()?;
+    }
+    Some(_) => {
+      let rows: Vec<&Value> = rows.iter().map(|(row, _)| row).collect();
+      writeln!(out, "{}", serde_yaml::to_string(&rows).into_diagnostic()?).into_diagnostic()?;
+    }
+  }
+  out.flush().into_diagnostic()?;
+  Err(Printed(0).into())
+}
+
+/// `extra_action` of the other results: prints the result rendered by `--output-template`.
+///
+/// [Printed] is returned once it is printed. Without template, it is printed by crud-api.
+pub(crate) fn output_result<T: Serialize>(result: &T, _settings: &Config) -> Result<()> {
+  let matches = Session::current()?.subcommands().1;
+  if pagination::requested(matches) {
+    pagination::print_all(std::slice::from_ref(result))?;
+    return Err(Printed(0).into());
+  }
+  let Some(output_template) = raw_value(matches, "output_template") else {
+    return Ok(());
+  };
+  template::render(&template::source(output_template)?, &[result])?;
+  Err(Printed(0).into())
+}
+
+/// Prints the result completed by its `extra_action`, with its template or in its output
+/// format: crud-api would print the result as it was received.
+pub(crate) fn output_joined_result<T>(result: &T, settings: &Config) -> Result<()>
+where
+  T: Api + Serialize + Debug,
+{
+  output_result(result, settings)?;
+  let matches = Session::current()?.subcommands().1;
+  result.output(crud_api::clap_match_output_format(matches))?;
+  Err(Printed(0).into())
+}
+
+/// Value of the argument as given in the command line: the parsed `output_format` is `json`
+/// for all the formats added here.
+fn raw_value<'a>(matches: &'a ArgMatches, id: &str) -> Option<&'a str> {
+  matches
+    .try_get_raw(id)
+    .ok()
+    .flatten()
+    .and_then(|mut values| values.next())
+    .and_then(|value| value.to_str())
+}
+
+/// Name of the resource in the settings: `merge_request` for `MergeRequest`.
+fn resource_name<T>() -> String {
+  let type_name = std::any::type_name::<T>();
+  let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+  let mut name = String::new();
+  for (index, c) in type_name.chars().enumerate() {
+    if c.is_uppercase() {
+      if index > 0 {
+        name.push('_');
+      }
+      name.extend(c.to_lowercase());
+    } else {
+      name.push(c);
+    }
+  }
+  name
+}
+
+fn save_columns(settings: &Config, resource: &str, columns: &str) -> Result<()> {
+  profiles::edit_settings(settings, |doc| {
+    doc
+      .entry(COLUMNS_SETTING)
+      .or_insert(Item::Table(Table::new()))
+      .as_table_mut()
+      .ok_or_else(|| {
+        miette::miette!("`{COLUMNS_SETTING}` is not a table in the configuration file.")
+      })?
+      .insert(resource, value(columns));
+    Ok(())
+  })
+}
+
+/// Value of the field `path`: `author.username`. The items of the arrays are given by their index.
+fn lookup<'a>(value: &'a Value, path: &str) -> &'a Value {
+  path
+    .split('.')
+    .try_fold(value, |value, key| match value {
+      Value::Object(object) => object.get(key),
+      Value::Array(array) => key.parse::<usize>().ok().and_then(|index| array.get(index)),
+      _ => None,
+    })
+    .unwrap_or(&Value::Null)
+}
+
+/// Text of a cell of the table: like the tables of crud-api, the lists are joined.
+fn cell(value: &Value) -> String {
+  match value {
+    Value::Null => String::new(),
+    Value::String(text) => text.clone(),
+    Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(", "),
+    value => value.to_string(),
+  }
+}
+
+/// Numbers are compared by value, the other values by their text. The missing values come first.
+fn compare(a: &Value, b: &Value) -> Ordering {
+  match (a, b) {
+    (Value::Null, Value::Null) => Ordering::Equal,
+    (Value::Null, _) => Ordering::Less,
+    (_, Value::Null) => Ordering::Greater,
+    (Value::Number(a), Value::Number(b)) => a
+      .as_f64()
+      .partial_cmp(&b.as_f64())
+      .unwrap_or(Ordering::Equal),
+    (a, b) => cell(a).cmp(&cell(b)),
+  }
+}
+
+/// Writes a CSV record. The fields are quoted when needed, as described by the RFC 4180.
+fn write_csv_record(out: &mut impl Write, fields: &[String]) -> Result<()> {
+  let record: Vec<String> = fields
+    .iter()
+    .map(|field| {
+      if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+      } else {
+        field.clone()
+      }
+    })
+    .collect();
+  write!(out, "{}\r\n", record.join(",")).into_diagnostic()
+}
@@ -0,0 +1,348 @@
+//! `--all` and `--max-items`: follows the pages of a list and prints its items as JSON lines,
+//! or rendered by `--output-template`.
+//!
+//! The first page is fetched by the generated command, with 100 items by default, and
+//! printed by the `extra_action` of the list with [print_all]. The next pages are fetched
+//! by the session: the route of the list is the path of the matched subcommands, as the
+//! routes of the lists are the same as their CLI routes, and the query is made from the
+//! arguments given in the command line.
+//!
+//! The lists of [KEYSET_ROUTES] are followed with the keyset pagination, as the offset
+//! pagination is limited for the large lists: the pages are asked by `pagination=keyset`,
+//! ordered by `id`, and the next ones are given by the `Link` header. The first page,
+//! fetched by the generated command with the offset pagination, is asked again. The lists
+//! ordered by another field than `id`, or started from another page, are followed with the
+//! offset pagination.
+
+use crate::{ids::encode, output, session::Session, template};
+use clap::{
+  builder::ArgPredicate, parser::ValueSource, value_parser, Arg, ArgAction, ArgMatches, Command,
+};
+use hyper::header::{HeaderMap, LINK};
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+  io::{stdout, ErrorKind, StdoutLock, Write},
+  sync::OnceLock,
+};
+
+/// Number of items asked by page. It's the maximum allowed by Gitlab.
+const PER_PAGE: &str = "100";
+/// Arguments of the commands that are not sent in the query, besides [output::ARGS].
+const NOT_QUERY: [&str; 5] = [
+  "all",
+  "max_items",
+  "output_format",
+  "output_file",
+  "input_file",
+];
+
+/// Routes of the lists that offer the keyset pagination.
+const KEYSET_ROUTES: [&str; 2] = ["/projects", "/events"];
+
+/// Commands declared by crud-api, recorded when the pages are followed.
+static COMMAND: OnceLock<Command> = OnceLock::new();
+
+/// Declares `--all` and `--max-items` on the commands that have a `--page` argument.
+pub(crate) fn declare(command: Command) -> Command {
+  let command = if command.get_arguments().any(|arg| arg.get_id() == "page") {
+    let command = if command
+      .get_arguments()
+      .any(|arg| arg.get_id() == "per_page")
+    {
+      // The first page, fetched by the generated command, is as large as the next ones.
+      command.mut_arg("per_page", |arg| {
+        arg
+          .default_value_if("all", "true", Some(PER_PAGE))
+          .default_value_if("max_items", ArgPredicate::IsPresent, Some(PER_PAGE))
+      })
+    } else {
+      command
+    };
+    command
+      .arg(
+        Arg::new("all")
+          .long("all")
+          .action(ArgAction::SetTrue)
+          .help_heading("Pagination")
+          .help("Get all the pages. The items are printed as JSON lines."),
+      )
+      .arg(
+        Arg::new("max_items")
+          .long("max-items")
+          .value_name("N")
+          .value_parser(value_parser!(usize))
+          .help_heading("Pagination")
+          .help("Get the pages until N items. The items are printed as JSON lines."),
+      )
+  } else {
+    command
+  };
+
+  let names: Vec<String> = command
+    .get_subcommands()
+    .map(|subcommand| subcommand.get_name().to_string())
+    .collect();
+  names.iter().fold(command, |command, name| {
+    command.mut_subcommand(name, declare)
+  })
+}
+
+/// `true` when `--all` or `--max-items` is given to the last subcommand.
+pub(crate) fn requested(matches: &ArgMatches) -> bool {
+  matches!(matches.try_get_one::<bool>("all"), Ok(Some(true)))
+    || matches!(matches.try_get_one::<usize>("max_items"), Ok(Some(_)))
+}
+
+/// Records the commands declared by crud-api: the route and the query of the list are
+/// read from them.
+pub(crate) fn record(command: &Command) {
+  let _ = COMMAND.set(command.clone());
+}
+
+/// Prints the items of the `first_page`, fetched by the generated command, and of the next
+/// pages, until the last one or `--max-items` items.
+pub(crate) fn print_all<T: Serialize>(first_page: &[T]) -> Result<()> {
+  let session = Session::current()?;
+  let first_page = first_page
+    .iter()
+    .map(serde_json::to_value)
+    .collect::<Result<Vec<_>, _>>()
+    .into_diagnostic()?;
+  tokio::task::block_in_place(|| {
+    tokio::runtime::Handle::current().block_on(follow(session, first_page))
+  })
+}
+
+async fn follow(session: &Session, first_page: Vec<Value>) -> Result<()> {
+  let command = COMMAND
+    .get()
+    .ok_or_else(|| miette!("The commands are not recorded."))?;
+  let (route, mut query) = route_and_query(command, session.matches())?;
+  let (_, leaf) = session.subcommands();
+  let output_template = leaf
+    .try_get_one::<String>("output_template")
+    .ok()
+    .flatten()
+    .map(|output_template| template::source(output_template))
+    .transpose()?;
+  let mut printer = Printer {
+    out: stdout().lock(),
+    count: 0,
+    max_items: leaf
+      .try_get_one::<usize>("max_items")
+      .ok()
+      .flatten()
+      .copied(),
+    output_template,
+  };
+
+  // `--per-page` is given, or set to [PER_PAGE] by `--all` and `--max-items`.
+  let number = |id: &str| {
+    leaf
+      .try_get_raw(id)
+      .ok()
+      .flatten()
+      .and_then(|mut values| values.next())
+      .and_then(|value| value.to_str()?.parse::<usize>().ok())
+  };
+  let per_page = number("per_page").unwrap_or(100);
+  let page = number("page").unwrap_or(1);
+  query.retain(|(key, _)| key != "page" && key != "per_page");
+  query.push(("per_page".into(), per_page.to_string()));
+
+  let keyset = page == 1
+    && KEYSET_ROUTES.contains(&route.as_str())
+    && query
+      .iter()
+      .all(|(key, value)| key != "order_by" || value == "id");
+  let mut next = if keyset {
+    // The cursor of the next page is given by the `Link` header of the first page.
+    query.retain(|(key, _)| key != "order_by");
+    query.extend([
+      ("pagination".into(), "keyset".into()),
+      ("order_by".into(), "id".into()),
+    ]);
+    if !query.iter().any(|(key, _)| key == "sort") {
+      query.push(("sort".into(), "asc".into()));
+    }
+    Some(format!("{route}?{}", query_string(&query)))
+  } else {
+    // A page is the last one when it is not full.
+    let next = (first_page.len() >= per_page).then(|| {
+      let mut query = query.clone();
+      query.push(("page".into(), (page + 1).to_string()));
+      format!("{route}?{}", query_string(&query))
+    });
+    if !printer.print(first_page)? {
+      return Ok(());
+    }
+    next
+  };
+  while let Some(url) = next.take() {
+    let response = session.get_raw(&url, &[]).await?;
+    let items: Value = serde_json::from_slice(&response.body)
+      .into_diagnostic()
+      .wrap_err("Can't deserialize the response")?;
+    let items = match items {
+      Value::Array(items) => items,
+      // Not a list: the command doesn't end with a list.
+      item => vec![item],
+    };
+    if !printer.print(items)? {
+      break;
+    }
+    next = if keyset {
+      next_link(&route, &response.headers)
+    } else {
+      next_page(&route, &query, &response.headers)
+    };
+  }
+  Ok(())
+}
+
+/// Items of all the pages of the list `route`.
+pub(crate) async fn get_all<T: DeserializeOwned>(session: &Session, route: &str) -> Result<Vec<T>> {
+  let query = [("per_page".to_string(), PER_PAGE.to_string())];
+  let mut items = vec![];
+  let mut next = Some(format!("{route}?{}", query_string(&query)));
+  while let Some(url) = next.take() {
+    let response = session.get_raw(&url, &[]).await?;
+    let page: Vec<T> = serde_json::from_slice(&response.body)
+      .into_diagnostic()
+      .wrap_err("Can't deserialize the response")?;
+    items.extend(page);
+    next = next_page(route, &query, &response.headers);
+  }
+  Ok(items)
+}
+
+/// Prints the items as JSON lines or rendered by the template.
+struct Printer {
+  out: StdoutLock<'static>,
+  count: usize,
+  max_items: Option<usize>,
+  output_template: Option<String>,
+}
+
+impl Printer {
+  /// Prints the `items`. Returns `false` once `--max-items` items are printed, or when
+  /// the output is closed.
+  fn print(&mut self, items: Vec<Value>) -> Result<bool> {
+    for item in items {
+      if self
+        .max_items
+        .is_some_and(|max_items| self.count >= max_items)
+      {
+        return Ok(false);
+      }
+      let written = match &self.output_template {
+        Some(output_template) => {
+          let rendered = template::render_to_string(output_template, &item)?;
+          writeln!(self.out, "{rendered}")
+        }
+        None => writeln!(self.out, "{item}"),
+      };
+      match written {
+        Err(err) if err.kind() == ErrorKind::BrokenPipe => return Ok(false),
+        result => result.into_diagnostic()?,
+      }
+      self.count += 1;
+    }
+    self.out.flush().into_diagnostic()?;
+    Ok(
+      self
+        .max_items
+        .is_none_or(|max_items| self.count < max_items),
+    )
+  }
+}
+
+/// Route of the list and query given by the arguments.
+///
+/// `projects 42 issues --state opened` gives `/projects/42/issues` and `state=opened`.
+fn route_and_query(
+  command: &Command,
+  matches: &ArgMatches,
+) -> Result<(String, Vec<(String, String)>)> {
+  let mut route = String::new();
+  let mut command = command;
+  let mut matches = matches;
+  while let Some((name, sub_matches)) = matches.subcommand() {
+    command = command
+      .find_subcommand(name)
+      .ok_or_else(|| miette!("Unknown command `{name}`"))?;
+    route.push('/');
+    route.push_str(name);
+    for arg in command.get_positionals() {
+      if let Ok(Some(value)) = sub_matches.try_get_one::<String>(arg.get_id().as_str()) {
+        route.push('/');
+        route.push_str(value);
+      }
+    }
+    matches = sub_matches;
+  }
+
+  let mut query = vec![];
+  for arg in command.get_arguments() {
+    let id = arg.get_id().as_str();
+    if arg.is_positional() || NOT_QUERY.contains(&id) || output::ARGS.contains(&id) {
+      continue;
+    }
+    if !matches!(
+      matches.value_source(id),
+      Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    ) {
+      continue;
+    }
+    // `_ref` is the field of `ref`.
+    let name = id.trim_start_matches('_');
+    let name = if matches!(arg.get_action(), ArgAction::Append) {
+      format!("{name}[]")
+    } else {
+      name.to_string()
+    };
+    for value in matches.get_raw(id).into_iter().flatten() {
+      query.push((name.clone(), value.to_string_lossy().to_string()));
+    }
+  }
+  Ok((route, query))
+}
+
+/// Next page given by the `Link` header, or else by the `X-Next-Page` header.
+fn next_page(route: &str, query: &[(String, String)], headers: &HeaderMap) -> Option<String> {
+  if let Some(next) = next_link(route, headers) {
+    return Some(next);
+  }
+
+  let page = headers
+    .get("x-next-page")
+    .and_then(|page| page.to_str().ok())
+    .filter(|page| !page.is_empty())?;
+  let mut query: Vec<(String, String)> = query
+    .iter()
+    .filter(|(key, _)| key != "page")
+    .cloned()
+    .collect();
+  query.push(("page".into(), page.into()));
+  Some(format!("{route}?{}", query_string(&query)))
+}
+
+/// Next page given by the `Link` header.
+fn next_link(route: &str, headers: &HeaderMap) -> Option<String> {
+  // <https://gitlab.example.com/api/v4/projects?page=2&per_page=100>; rel="next", <...>; rel="first"
+  let link = headers.get(LINK)?.to_str().ok()?;
+  let next = link.split(',').find(|part| part.contains("rel=\"next\""))?;
+  let url = next.trim().trim_start_matches('<').split('>').next()?;
+  // The host of the link can differ from the base URL: only its query is kept.
+  Some(format!("{route}?{}", url.split_once('?')?.1))
+}
+
+fn query_string(query: &[(String, String)]) -> String {
+  query
+    .iter()
+    .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+    .collect::<Vec<_>>()
+    .join("&")
+}
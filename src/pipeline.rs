@@ -1,9 +1,17 @@
+mod schedules;
 mod test_report;
 mod variables;
+mod watch;
 
 pub(crate) use self::{
+  schedules::{
+    PipelineSchedule, PipelineScheduleCreatePayload, PipelineScheduleEditPayload,
+    PipelineScheduleFilter, PipelineScheduleVariable, PipelineScheduleVariableCreatePayload,
+    PipelineScheduleVariableEditPayload,
+  },
   test_report::{TestReport, TestReportSummary},
   variables::Variables,
+  watch::{watch_pipeline, WatchQuery},
 };
 use crate::user::User;
 use crud_api::{Api, ApiInput};
@@ -14,6 +22,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/pipelines",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "PipelineFilter",
     cli_route = "/projects/{id}/pipelines",
     cli_help = "Get a list of pipelines in a project."
@@ -23,6 +32,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "PipelineCreate",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/pipelines/create",
     cli_help = "Create a new pipeline"
   ),
@@ -30,20 +40,31 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/pipelines/{pipeline_id}/retry",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/pipelines/{pipeline_id}/retry",
     cli_help = "Retry jobs in a pipeline"
   ),
   endpoint(
     route = "/projects/{id}/pipelines/{pipeline_id}/cancel",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/pipelines/{pipeline_id}/cancel",
     cli_help = "Cancel a pipeline's jobs"
   ),
   endpoint(
     route = "/projects/{id}/pipelines/{pipeline_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/pipelines/{pipeline_id}",
     cli_help = "Get a single pipeline."
   ),
+  endpoint(
+    route = "/projects/{id}/pipelines/{pipeline_id}",
+    query_struct = "WatchQuery",
+    extra_action = "watch_pipeline",
+    cli_route = "/projects/{id}/pipelines/{pipeline_id}/watch",
+    cli_help = "Watch the jobs of a pipeline until it is finished.",
+    cli_long_help = "Watch the jobs of a pipeline, grouped by stage, until it is finished. The jobs of the downstream pipelines are shown under their bridge. The exit code is the final status of the pipeline: 0 for `success`, 1 for `failed`, 2 for `canceled`, 3 for `skipped` and 4 for `manual`."
+  ),
   endpoint(
     route = "/projects/{id}/pipelines/{pipeline_id}",
     method = "DELETE",
@@ -56,6 +77,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/pipelines",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/merge_requests/{iid}/pipelines",
     cli_help = "Get a list of merge request pipelines",
   ),
@@ -63,6 +85,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/merge_requests/{iid}/pipelines",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/pipelines/create",
     cli_help = "Create a new pipeline for a merge request.",
     cli_long_help = "Create a new pipeline for a merge request. A pipeline created via this endpoint doesn't run a regular branch/tag pipeline. It requires .gitlab-ci.yml to be configured with only: [merge_requests] to create jobs."
@@ -70,6 +93,7 @@ use serde::{Deserialize, Serialize};
 )]
 pub(crate) struct Pipeline {
   id: u32,
+  #[serde(default)]
   project_id: u32,
   #[api(table_skip)]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -79,10 +103,14 @@ pub(crate) struct Pipeline {
   #[serde(rename = "ref")]
   _ref: String,
   status: String,
+  // The last pipeline of a schedule may only have its id, sha, ref and status.
+  #[serde(default)]
   created_at: String,
   #[api(table_skip)]
+  #[serde(default)]
   updated_at: String,
   #[api(table_skip)]
+  #[serde(default)]
   web_url: String,
   #[api(table_skip)]
   #[serde(skip_serializing_if = "Option::is_none")]
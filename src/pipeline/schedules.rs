@@ -0,0 +1,262 @@
+use super::Pipeline;
+use crate::user::User;
+use crud_api::{Api, ApiInput};
+use serde::{Deserialize, Serialize};
+
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "PipelineScheduleFilter",
+    cli_route = "/projects/{id}/pipeline_schedules",
+    cli_help = "Get a list of the pipeline schedules of a project.",
+    cli_long_help = "Get a list of the pipeline schedules of a project. Their last pipeline can be shown in the table: `--columns id,description,cron,active,last_pipeline.status`."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}",
+    cli_help = "Get the pipeline schedule of a project, with its last pipeline and its variables."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules",
+    method = "POST",
+    payload_struct = "PipelineScheduleCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/create",
+    cli_help = "Create a new pipeline schedule of a project."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}",
+    method = "PUT",
+    payload_struct = "PipelineScheduleEditPayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/edit",
+    cli_help = "Updates the pipeline schedule of a project."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/take_ownership",
+    method = "POST",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/take_ownership",
+    cli_help = "Update the owner of the pipeline schedule of a project."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/delete",
+    cli_help = "Delete the pipeline schedule of a project."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/play",
+    method = "POST",
+    result_ok_status = "CREATED",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/play",
+    cli_help = "Trigger a new scheduled pipeline, which runs immediately.",
+    cli_long_help = "Trigger a new scheduled pipeline, which runs immediately. The next scheduled run of this pipeline is not affected."
+  )
+)]
+pub(crate) struct PipelineSchedule {
+  id: u32,
+  description: String,
+  #[serde(rename = "ref")]
+  _ref: String,
+  cron: String,
+  cron_timezone: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  next_run_at: Option<String>,
+  active: bool,
+  #[api(table_skip)]
+  created_at: String,
+  #[api(table_skip)]
+  updated_at: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  owner: Option<User>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  last_pipeline: Option<Pipeline>,
+  /// Only given by the details of a schedule.
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  variables: Option<Vec<PipelineScheduleVariable>>,
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables",
+    method = "POST",
+    payload_struct = "PipelineScheduleVariableCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/create",
+    cli_help = "Create a new variable of a pipeline schedule."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/{var_key}",
+    method = "PUT",
+    payload_struct = "PipelineScheduleVariableEditPayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/{var_key}/edit",
+    cli_help = "Updates the variable of a pipeline schedule."
+  ),
+  endpoint(
+    route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/{var_key}",
+    method = "DELETE",
+    result_ok_status = "ACCEPTED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/{var_key}/delete",
+    cli_help = "Delete the variable of a pipeline schedule."
+  )
+)]
+pub(crate) struct PipelineScheduleVariable {
+  key: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  variable_type: Option<String>,
+  value: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  raw: Option<bool>,
+}
+
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct PipelineScheduleFilter {
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "The scope of pipeline schedules.",
+    possible_values = "active,inactive"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  scope: Option<String>,
+}
+
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct PipelineScheduleCreatePayload {
+  #[api(no_short, help = "The description of the pipeline schedule.")]
+  description: String,
+
+  #[api(
+    no_short,
+    long = "ref",
+    help = "The branch or tag name that is triggered."
+  )]
+  #[serde(rename = "ref")]
+  _ref: String,
+
+  #[api(
+    no_short,
+    help = "The cron schedule, for example: `0 1 * * *`.",
+    long_help = "The cron schedule, for example: `0 1 * * *`. See https://en.wikipedia.org/wiki/Cron"
+  )]
+  cron: String,
+
+  #[api(
+    no_short,
+    long = "cron-timezone",
+    help = "The time zone of the cron schedule (default: UTC).",
+    long_help = "The time zone supported by ActiveSupport::TimeZone, for example: `Pacific Time (US & Canada)` (default: UTC)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  cron_timezone: Option<String>,
+
+  /// A string, like in [PipelineScheduleEditPayload]: the default is `true`.
+  #[api(
+    no_short,
+    help = "The activation of pipeline schedule (default: true).",
+    long_help = "The activation of pipeline schedule. If false is set, the pipeline schedule is initially deactivated (default: true).",
+    possible_values = "true,false"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  active: Option<String>,
+}
+
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct PipelineScheduleEditPayload {
+  #[api(no_short, help = "The description of the pipeline schedule.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+
+  #[api(
+    no_short,
+    long = "ref",
+    help = "The branch or tag name that is triggered."
+  )]
+  #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+  _ref: Option<String>,
+
+  #[api(no_short, help = "The cron schedule, for example: `0 1 * * *`.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  cron: Option<String>,
+
+  #[api(
+    no_short,
+    long = "cron-timezone",
+    help = "The time zone of the cron schedule.",
+    long_help = "The time zone supported by ActiveSupport::TimeZone, for example: `Pacific Time (US & Canada)`."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  cron_timezone: Option<String>,
+
+  /// A string: a flag could not deactivate the schedule. Gitlab parses it as a boolean.
+  #[api(
+    no_short,
+    help = "Activate (`true`) or deactivate (`false`) the pipeline schedule.",
+    possible_values = "true,false"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  active: Option<String>,
+}
+
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct PipelineScheduleVariableCreatePayload {
+  #[api(
+    no_short,
+    help = "The key of a variable.",
+    long_help = "The key of a variable; must have no more than 255 characters; only A-Z, a-z, 0-9, and _ are allowed"
+  )]
+  key: String,
+
+  #[api(no_short, help = "The value of a variable")]
+  value: String,
+
+  #[api(
+    no_short,
+    long = "variable-type",
+    help = "The type of a variable.",
+    long_help = "The type of a variable. Available types are: env_var (default) and file",
+    possible_values = "env_var,file"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  variable_type: Option<String>,
+}
+
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct PipelineScheduleVariableEditPayload {
+  #[api(no_short, help = "The value of a variable")]
+  value: String,
+
+  #[api(
+    no_short,
+    long = "variable-type",
+    help = "The type of a variable.",
+    long_help = "The type of a variable. Available types are: env_var (default) and file",
+    possible_values = "env_var,file"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  variable_type: Option<String>,
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Api, Serialize, Deserialize, Debug, Default)]
 #[api(endpoint(
   route = "/projects/{id}/pipelines/{pipeline_id}/test_report",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/pipelines/{pipeline_id}/test_report",
   cli_help = "Get a pipeline's test report"
 ))]
@@ -50,6 +51,7 @@ struct TestCase {
 #[derive(Api, Serialize, Deserialize, Debug, Default)]
 #[api(endpoint(
   route = "/projects/{id}/pipelines/{pipeline_id}/test_report_summary",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/pipelines/{pipeline_id}/test_report_summary",
   cli_help = "Get a pipeline's test report summary"
 ))]
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/projects/{id}/pipelines/{pipeline_id}/variables",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/projects/{id}/pipelines/{pipeline_id}/variables",
   cli_help = "Get variables of a pipeline."
 ))]
@@ -0,0 +1,257 @@
+//! `pipelines {pipeline_id} watch`: redraws the jobs of a pipeline until it is finished.
+
+use super::Pipeline;
+use crate::{jobs::status_exit_code, output::Printed, pagination, session::Session};
+use config::Config;
+use crud_api::ApiInput;
+use is_terminal::IsTerminal;
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashMap,
+  io::{stdout, Write},
+  time::Duration,
+};
+
+/// Seconds between two refreshes of the pipeline.
+const DEFAULT_INTERVAL: u64 = 5;
+/// Width of the usual job statuses (`canceled`, `pending`, `success`...).
+const STATUS_WIDTH: usize = 8;
+
+/// Options of the watch. They are not sent to Gitlab.
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct WatchQuery {
+  #[api(
+    no_short,
+    heading = "Watch",
+    help = "Seconds between two refreshes of the pipeline (default: 5)."
+  )]
+  #[serde(skip)]
+  interval: Option<u64>,
+}
+
+/// Job or bridge of a pipeline.
+#[derive(Debug, Default, Deserialize)]
+struct WatchedJob {
+  id: u32,
+  name: String,
+  stage: String,
+  status: String,
+  duration: Option<f64>,
+  downstream_pipeline: Option<DownstreamPipeline>,
+}
+
+/// Pipeline triggered by a bridge.
+#[derive(Debug, Default, Deserialize)]
+struct DownstreamPipeline {
+  id: u32,
+  project_id: u32,
+  status: String,
+}
+
+/// Jobs of a pipeline and the jobs of its downstream pipelines, by bridge id.
+#[derive(Default)]
+struct JobTree {
+  jobs: Vec<WatchedJob>,
+  downstreams: HashMap<u32, JobTree>,
+}
+
+/// `extra_action` of `pipelines {pipeline_id} watch`.
+///
+/// Returns [Printed] with the exit code given by the final status of the pipeline.
+pub(crate) fn watch_pipeline(pipeline: &Pipeline, _settings: &Config) -> Result<()> {
+  let session = Session::current()?;
+  let options = WatchQuery::from_clap_matches(session.subcommands().1)?;
+  let interval = Duration::from_secs(options.interval.unwrap_or(DEFAULT_INTERVAL));
+  let exit_code = tokio::task::block_in_place(|| {
+    tokio::runtime::Handle::current().block_on(watch(
+      session,
+      pipeline.project_id,
+      pipeline.id,
+      interval,
+    ))
+  })?;
+  Err(Printed(exit_code as u8).into())
+}
+
+async fn watch(
+  session: &Session,
+  project_id: u32,
+  pipeline_id: u32,
+  interval: Duration,
+) -> Result<i32> {
+  let mut screen = Screen::new(stdout().is_terminal());
+  loop {
+    let pipeline: Pipeline = session
+      .get(&format!("/projects/{project_id}/pipelines/{pipeline_id}"))
+      .await?;
+    let tree = fetch_tree(session, project_id, pipeline_id).await?;
+
+    let mut lines = vec![format!(
+      "Pipeline #{} ({}) {}",
+      pipeline.id,
+      pipeline._ref,
+      screen.status(&pipeline.status, 0)
+    )];
+    render(&tree, "", &screen, &mut lines);
+    screen.draw(lines)?;
+
+    if let Some(exit_code) = status_exit_code(&pipeline.status) {
+      return Ok(exit_code);
+    }
+    tokio::time::sleep(interval).await;
+  }
+}
+
+/// Fetches the jobs and bridges of a pipeline, and the jobs of the downstream pipelines.
+async fn fetch_tree(session: &Session, project_id: u32, pipeline_id: u32) -> Result<JobTree> {
+  let route = format!("/projects/{project_id}/pipelines/{pipeline_id}");
+  let mut jobs: Vec<WatchedJob> = pagination::get_all(session, &format!("{route}/jobs")).await?;
+  let bridges: Vec<WatchedJob> = pagination::get_all(session, &format!("{route}/bridges")).await?;
+  jobs.extend(bridges);
+  // Jobs are created stage after stage.
+  jobs.sort_by_key(|job| job.id);
+
+  let mut downstreams = HashMap::new();
+  for job in &jobs {
+    if let Some(downstream) = &job.downstream_pipeline {
+      let tree = Box::pin(fetch_tree(session, downstream.project_id, downstream.id)).await?;
+      downstreams.insert(job.id, tree);
+    }
+  }
+  Ok(JobTree { jobs, downstreams })
+}
+
+/// Draws the jobs grouped by stage.
+fn render(tree: &JobTree, prefix: &str, screen: &Screen, lines: &mut Vec<String>) {
+  let mut stages: Vec<(&str, Vec<&WatchedJob>)> = vec![];
+  for job in &tree.jobs {
+    match stages.iter_mut().find(|(stage, _)| *stage == job.stage) {
+      Some((_, jobs)) => jobs.push(job),
+      None => stages.push((&job.stage, vec![job])),
+    }
+  }
+
+  let name_width = tree
+    .jobs
+    .iter()
+    .map(|job| job.name.chars().count())
+    .max()
+    .unwrap_or_default();
+  for (stage_index, (stage, jobs)) in stages.iter().enumerate() {
+    let last_stage = stage_index + 1 == stages.len();
+    lines.push(format!("{prefix}{}{stage}", branch(last_stage)));
+    let stage_prefix = format!("{prefix}{}", indent(last_stage));
+    for (job_index, job) in jobs.iter().enumerate() {
+      let last_job = job_index + 1 == jobs.len();
+      let mut line = format!(
+        "{stage_prefix}{}{:<name_width$}  {}",
+        branch(last_job),
+        job.name,
+        screen.status(&job.status, STATUS_WIDTH)
+      );
+      if let Some(duration) = job
+        .duration
+        .filter(|_| status_exit_code(&job.status).is_some())
+      {
+        line.push_str(&format!("  {}", format_duration(duration)));
+      }
+      if let Some(downstream) = &job.downstream_pipeline {
+        line.push_str(&format!(
+          "  → Pipeline #{} {}",
+          downstream.id,
+          screen.status(&downstream.status, 0)
+        ));
+      }
+      lines.push(line.trim_end().to_string());
+      if let Some(downstream) = tree.downstreams.get(&job.id) {
+        render(
+          downstream,
+          &format!("{stage_prefix}{}", indent(last_job)),
+          screen,
+          lines,
+        );
+      }
+    }
+  }
+}
+
+fn branch(last: bool) -> &'static str {
+  if last {
+    "└─ "
+  } else {
+    "├─ "
+  }
+}
+
+fn indent(last: bool) -> &'static str {
+  if last {
+    "   "
+  } else {
+    "│  "
+  }
+}
+
+fn format_duration(seconds: f64) -> String {
+  let seconds = seconds.round() as u64;
+  if seconds >= 60 {
+    format!("{}m {:02}s", seconds / 60, seconds % 60)
+  } else {
+    format!("{seconds}s")
+  }
+}
+
+/// Output of the watch.
+///
+/// On a terminal, the previous drawing is replaced. Otherwise, the tree is
+/// printed again each time it changes.
+struct Screen {
+  terminal: bool,
+  previous: Vec<String>,
+}
+
+impl Screen {
+  fn new(terminal: bool) -> Self {
+    Screen {
+      terminal,
+      previous: vec![],
+    }
+  }
+
+  /// Icon and name of the status, padded to `width`.
+  fn status(&self, status: &str, width: usize) -> String {
+    let (icon, color) = match status {
+      "success" => ("✔", 32),
+      "failed" => ("✘", 31),
+      "running" => ("●", 34),
+      "canceled" | "skipped" => ("⊘", 90),
+      "manual" => ("⚙", 90),
+      _ => ("○", 33),
+    };
+    if self.terminal {
+      format!("\x1b[{color}m{icon} {status:<width$}\x1b[0m")
+    } else {
+      format!("{icon} {status:<width$}")
+    }
+  }
+
+  fn draw(&mut self, lines: Vec<String>) -> Result<()> {
+    if lines == self.previous {
+      return Ok(());
+    }
+    let mut out = stdout().lock();
+    if self.terminal && !self.previous.is_empty() {
+      // Moves to the first line of the previous drawing and clears the screen below.
+      write!(out, "\x1b[{}F\x1b[J", self.previous.len()).into_diagnostic()?;
+    } else if !self.previous.is_empty() {
+      writeln!(out).into_diagnostic()?;
+    }
+    for line in &lines {
+      writeln!(out, "{line}").into_diagnostic()?;
+    }
+    out.flush().into_diagnostic()?;
+    self.previous = lines;
+    Ok(())
+  }
+}
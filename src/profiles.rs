@@ -0,0 +1,350 @@
+//! Named profiles of the configuration file and the `config` command.
+//!
+//! A profile is a `[profile.<name>]` table of `settings.toml`:
+//!
+//! ```toml
+//! current_profile = "work"
+//!
+//! [profile.work]
+//! base_url = "https://gitlab.example.com/api/v4"
+//! auth_token = "glpat-..."
+//! format = "json"
+//! project = "42"
+//! ```
+//!
+//! `base_url` and `auth_token` are read by `crud-api` when the `--profile` argument
+//! is set. This module makes `current_profile` the default value of `--profile`
+//! and applies the other defaults of the profile to the commands.
+
+use crate::ids;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use config::Config;
+use miette::{miette, IntoDiagnostic, Result};
+use std::{fs, path::Path};
+use toml_edit::{table, value, Document, Item, Table};
+
+/// Must be the same as the `infos` declared in `main.rs`.
+const QUALIFIER: &str = "org";
+const ORGANISATION: &str = "djedi";
+const APPLICATION: &str = "gitlab-cli";
+const ENV_PREFIX: &str = "GITLAB";
+
+const CURRENT_PROFILE_SETTING: &str = "current_profile";
+const FORMATS: [&str; 5] = ["json", "toml", "yaml", "csv", "ndjson"];
+
+/// Settings of a named profile.
+#[derive(Debug, Default)]
+struct Profile {
+  base_url: Option<String>,
+  auth_token: Option<String>,
+  format: Option<String>,
+  project: Option<String>,
+}
+
+impl Profile {
+  fn load(settings: &Config, name: &str) -> Self {
+    let get = |key: &str| settings.get_string(&format!("profile.{name}.{key}")).ok();
+    Profile {
+      base_url: get("base_url"),
+      auth_token: get("auth_token"),
+      format: get("format"),
+      project: get("project"),
+    }
+  }
+}
+
+/// Settings of the CLI, as read by the generated commands.
+pub(crate) fn settings() -> Result<Config> {
+  crud_api::settings::settings(QUALIFIER, ORGANISATION, APPLICATION, ENV_PREFIX)
+}
+
+/// Applies the defaults of the active profile and the ids resolved by [ids::resolve].
+pub(crate) fn apply_defaults(command: Command) -> Command {
+  let mut command = ids::apply(command);
+  let Ok(settings) = settings() else {
+    return command;
+  };
+  if let Some(name) = active_profile(&settings) {
+    let profile = Profile::load(&settings, &name);
+    command = command.mut_arg("profile", |arg| arg.default_value(name));
+    if let Some(format) = profile.format {
+      command = default_format(command, &format);
+    }
+  }
+  command
+}
+
+/// Default project of the active profile.
+pub(crate) fn default_project(settings: &Config) -> Option<String> {
+  Profile::load(settings, &active_profile(settings)?).project
+}
+
+/// Name of the profile given by `--profile`, or else the current profile of the settings.
+///
+/// The arguments are not parsed yet: `--profile` is looked up in the raw arguments.
+fn active_profile(settings: &Config) -> Option<String> {
+  let mut args = std::env::args().skip(1);
+  while let Some(arg) = args.next() {
+    if arg == "--profile" {
+      return args.next();
+    } else if let Some(name) = arg.strip_prefix("--profile=") {
+      return Some(name.to_string());
+    }
+  }
+  settings.get_string(CURRENT_PROFILE_SETTING).ok()
+}
+
+/// Sets the default value of the `output_format` arguments that accept `format`.
+fn default_format(command: Command, format: &str) -> Command {
+  let accepted = command
+    .get_arguments()
+    .find(|arg| arg.get_id() == "output_format")
+    .map(|arg| {
+      arg
+        .get_possible_values()
+        .iter()
+        .any(|possible| possible.get_name() == format)
+    })
+    .unwrap_or(false);
+  let command = if accepted {
+    command.mut_arg("output_format", |arg| arg.default_value(format.to_string()))
+  } else {
+    command
+  };
+
+  let names: Vec<String> = command
+    .get_subcommands()
+    .map(|subcommand| subcommand.get_name().to_string())
+    .collect();
+  names.iter().fold(command, |command, name| {
+    command.mut_subcommand(name, |subcommand| default_format(subcommand, format))
+  })
+}
+
+/// Declaration of the `config` command.
+pub(crate) fn config_command() -> Command {
+  Command::new("config")
+    .about("Manage the profiles of the configuration file.")
+    .subcommand_required(true)
+    .subcommand(
+      Command::new("add")
+        .about("Add or update a profile.")
+        .long_about("Add or update a profile. Only the given settings are changed.")
+        .arg(Arg::new("name").required(true).help("Name of the profile."))
+        .arg(
+          Arg::new("host")
+            .long("host")
+            .action(ArgAction::Set)
+            .help("Host of the Gitlab instance.")
+            .long_help("Host of the Gitlab instance (`gitlab.example.com`) or URL of its API (`https://gitlab.example.com/api/v4`)."),
+        )
+        .arg(
+          Arg::new("token")
+            .long("token")
+            .action(ArgAction::Set)
+            .help("Authorization token."),
+        )
+        .arg(
+          Arg::new("format")
+            .long("format")
+            .action(ArgAction::Set)
+            .value_parser(FORMATS)
+            .help("Default output format."),
+        )
+        .arg(
+          Arg::new("project")
+            .long("project")
+            .action(ArgAction::Set)
+            .help("Default project.")
+            .long_help("Default project: its id or its path. Used when the project is omitted: `gitlab-cli projects pipelines`."),
+        )
+        .arg(
+          Arg::new("switch")
+            .long("switch")
+            .action(ArgAction::SetTrue)
+            .help("Make it the current profile."),
+        ),
+    )
+    .subcommand(Command::new("list").about("List the profiles."))
+    .subcommand(
+      Command::new("switch")
+        .about("Change the current profile.")
+        .long_about("Change the current profile. The current profile is used when `--profile` is not given.")
+        .arg(
+          Arg::new("name")
+            .required_unless_present("none")
+            .help("Name of the profile."),
+        )
+        .arg(
+          Arg::new("none")
+            .long("none")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("name")
+            .help("Don't use a profile by default."),
+        ),
+    )
+}
+
+/// Runs the `config` command. `matches` are the matches of the CLI.
+pub(crate) fn run_config(matches: &ArgMatches, settings: &Config) -> Result<()> {
+  match matches
+    .subcommand_matches("config")
+    .and_then(ArgMatches::subcommand)
+  {
+    Some(("add", matches)) => add(matches, settings),
+    Some(("list", _)) => list(settings),
+    Some(("switch", matches)) => switch(matches, settings),
+    _ => Ok(()),
+  }
+}
+
+fn add(matches: &ArgMatches, settings: &Config) -> Result<()> {
+  let name = matches
+    .get_one::<String>("name")
+    .cloned()
+    .unwrap_or_default();
+  edit_settings(settings, |doc| {
+    let mut profiles = Table::new();
+    profiles.set_implicit(true);
+    let profile = doc
+      .entry("profile")
+      .or_insert(Item::Table(profiles))
+      .as_table_mut()
+      .ok_or_else(|| miette!("`profile` is not a table in the configuration file."))?
+      .entry(&name)
+      .or_insert(table());
+    if let Some(host) = matches.get_one::<String>("host") {
+      profile["base_url"] = value(api_url(host));
+    }
+    if let Some(token) = matches.get_one::<String>("token") {
+      profile["auth_token"] = value(token);
+    }
+    if let Some(format) = matches.get_one::<String>("format") {
+      profile["format"] = value(format);
+    }
+    if let Some(project) = matches.get_one::<String>("project") {
+      profile["project"] = value(project);
+    }
+    if matches.get_flag("switch") {
+      doc[CURRENT_PROFILE_SETTING] = value(&name);
+    }
+    Ok(())
+  })?;
+  println!("Profile `{name}` saved.");
+  Ok(())
+}
+
+fn list(settings: &Config) -> Result<()> {
+  let current = settings.get_string(CURRENT_PROFILE_SETTING).ok();
+  let mut names: Vec<String> = settings
+    .get_table("profile")
+    .map(|profiles| profiles.into_keys().collect())
+    .unwrap_or_default();
+  names.sort();
+  if names.is_empty() {
+    println!("No profile. Add one with `gitlab-cli config add <name> --host <host>`.");
+    return Ok(());
+  }
+
+  let rows: Vec<[String; 6]> = names
+    .iter()
+    .map(|name| {
+      let profile = Profile::load(settings, name);
+      [
+        if current.as_ref() == Some(name) {
+          "*"
+        } else {
+          ""
+        }
+        .to_string(),
+        name.clone(),
+        profile.base_url.unwrap_or_default(),
+        if profile.auth_token.is_some() {
+          "set"
+        } else {
+          ""
+        }
+        .to_string(),
+        profile.format.unwrap_or_default(),
+        profile.project.unwrap_or_default(),
+      ]
+    })
+    .collect();
+  let header = ["", "NAME", "BASE URL", "TOKEN", "FORMAT", "PROJECT"].map(String::from);
+  let widths: Vec<usize> = (0..header.len())
+    .map(|column| {
+      std::iter::once(&header)
+        .chain(&rows)
+        .map(|row| row[column].chars().count())
+        .max()
+        .unwrap_or_default()
+    })
+    .collect();
+  for row in std::iter::once(&header).chain(&rows) {
+    let line: Vec<String> = row
+      .iter()
+      .zip(&widths)
+      .map(|(cell, width)| format!("{cell:<width$}"))
+      .collect();
+    println!("{}", line.join("  ").trim_end());
+  }
+  Ok(())
+}
+
+fn switch(matches: &ArgMatches, settings: &Config) -> Result<()> {
+  match matches.get_one::<String>("name") {
+    Some(name) => {
+      if settings.get_table(&format!("profile.{name}")).is_err() {
+        return Err(miette!(
+          "The profile `{name}` doesn't exist. Add it with `gitlab-cli config add {name}`."
+        ));
+      }
+      edit_settings(settings, |doc| {
+        doc[CURRENT_PROFILE_SETTING] = value(name);
+        Ok(())
+      })?;
+      println!("Switched to profile `{name}`.");
+    }
+    None => {
+      edit_settings(settings, |doc| {
+        doc.remove(CURRENT_PROFILE_SETTING);
+        Ok(())
+      })?;
+      println!("No current profile.");
+    }
+  }
+  Ok(())
+}
+
+/// Applies `edit` to the configuration file. The comments and the layout of the file are kept.
+pub(crate) fn edit_settings(
+  settings: &Config,
+  edit: impl FnOnce(&mut Document) -> Result<()>,
+) -> Result<()> {
+  let config_path = settings
+    .get_string("configuration_path")
+    .into_diagnostic()?;
+  let config_str = fs::read_to_string(&config_path).unwrap_or_default();
+
+  let mut doc = config_str.parse::<Document>().into_diagnostic()?;
+  edit(&mut doc)?;
+
+  if let Some(path) = Path::new(&config_path).parent() {
+    fs::create_dir_all(path).into_diagnostic()?;
+  }
+  fs::write(config_path, doc.to_string()).into_diagnostic()
+}
+
+/// URL of the API of a Gitlab instance: `gitlab.example.com` gives `https://gitlab.example.com/api/v4`.
+fn api_url(host: &str) -> String {
+  let url = if host.contains("://") {
+    host.to_string()
+  } else {
+    format!("https://{host}")
+  };
+  if url.contains("/api/") {
+    url
+  } else {
+    format!("{}/api/v4", url.trim_end_matches('/'))
+  }
+}
@@ -13,6 +13,7 @@ use std::fmt::Display;
   endpoint(
     route = "/projects",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "ProjectsFilter",
     cli_route = "/projects",
     cli_help = "Get a list of all visible projects across GitLab.",
@@ -20,6 +21,7 @@ use std::fmt::Display;
   endpoint(
     route = "/projects/{id}",
     query_struct = "ProjectDetailFilter",
+    extra_action = "output_result",
     cli_route = "/projects/{id}",
     cli_help = "Get a specific project.",
     cli_long_help = "Get a specific project. This endpoint can be accessed without authentication if the project is publicly accessible.",
@@ -29,6 +31,7 @@ use std::fmt::Display;
     method = "POST",
     payload_struct = "ProjectsCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/create",
     cli_help = "Creates a new project owned by the authenticated user.",
   ),
@@ -45,6 +48,7 @@ merge requests).",
     route = "/projects/{id}/restore",
     method = "POST",
 //    result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/restore",
     cli_help = "Restores project marked for deletion.",
   ),
@@ -55,6 +59,7 @@ merge requests).",
     method = "POST",
     query_struct = "ProjectsForkQuery",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/fork",
     cli_help = "Forks a project into the user namespace of the authenticated user or the one provided.",
     cli_long_help = "Forks a project into the user namespace of the authenticated user or the one provided.
@@ -63,6 +68,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
   endpoint(
     route = "/projects/{id}/forks",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "ProjectsForksQuery",
     cli_route = "/projects/{id}/forks",
     cli_help = "List the projects accessible to the calling user that have an established, forked relationship with the specified project",
@@ -92,6 +98,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
     method = "POST",
     result_ok_status = "CREATED",
     result_ko_status(status = "NOT_MODIFIED", message = "The project is already starred."),
+    extra_action = "output_result",
     cli_route = "/projects/{id}/star",
     cli_help = "Stars a given project.",
   ),
@@ -100,6 +107,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
     method = "POST",
     result_ok_status = "CREATED",
     result_ko_status(status = "NOT_MODIFIED", message = "The project is not starred."),
+    extra_action = "output_result",
     cli_route = "/projects/{id}/unstar",
     cli_help = "Unstars a given project.",
   )
@@ -109,6 +117,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
     route = "/projects/{id}/archive",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/archive",
     cli_help = "Archives the project if the user is either an administrator or the owner of this project.",
     cli_long_help = "Archives the project if the user is either an administrator or the owner of this project. This action is idempotent, thus archiving an already archived project does not change the project."
@@ -117,6 +126,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
     route = "/projects/{id}/unarchive",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/unarchive",
     cli_help = "Unarchives the project if the user is either an administrator or the owner of this project.",
     cli_long_help = "Unarchives the project if the user is either an administrator or the owner of this project. This action is idempotent, thus unarchiving a non-archived project doesn't change the project.",
@@ -147,6 +157,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
   route = "/projects/{id}/transfer",
   method = "PUT",
   query_struct = "TransferQuery",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/transfert",
   cli_help = "Transfer a project to a new namespace.",
 ))]
@@ -170,6 +181,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
   endpoint(
     route = "/users/{id}/projects",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "UsersProjectsFilter",
     cli_route = "/users/{id}/projects",
     cli_help = "Get a list of visible projects owned by the given user.",
@@ -178,6 +190,7 @@ The forking operation for a project is asynchronous and is completed in a backgr
   endpoint(
     route = "/users/{id}/starred_projects",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "ProjectsUsersFilter",
     cli_route = "/users/{id}/starred_projects",
     cli_help = "Get a list of visible projects owned by the given user.",
@@ -189,6 +202,7 @@ authentication, only public projects are returned."
   endpoint(
     route = "/groups/{id}/projects",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "ProjectsFilter",
     cli_route = "/groups/{id}/projects",
     cli_help = "Get a list of projects in this group.",
@@ -196,6 +210,7 @@ authentication, only public projects are returned."
   endpoint(
     route = "/groups/{id}/projects/shared",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "ProjectsFilter",
     cli_route = "/groups/{id}/projects/shared",
     cli_help = "Get a list of projects shared to this group.",
@@ -1,3 +1,5 @@
+pub mod transfer;
+
 use crud_api::Api;
 use crud_api::ApiInput;
 use crud_pretty_struct::PrettyPrint;
@@ -8,11 +10,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/variables",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/variables",
     cli_help = "Get list of a project's variables."
   ),
   endpoint(
     route = "/projects/{id}/variables/{var_key}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/variables/{var_key}",
     cli_long_help = "Get the details of a single variable. If there are multiple variables with the same key, use filter to select the correct environment_scope.",
     cli_help = "Get the details of a single variable."
@@ -22,6 +26,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "ProjectsVariableCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/variables/create",
     cli_long_help = "Create a new variable. If a variable with the same key already exists, the new variable
 must have a different environment_scope. Otherwise, GitLab returns a message similar to:
@@ -32,6 +37,7 @@ VARIABLE_NAME has already been taken.",
     route = "/projects/{id}/variables/{var_key}",
     method = "PUT",
     payload_struct = "ProjectsVariableUpdatePayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/variables/{var_key}/update",
     cli_long_help = "Update a project's variable. If there are multiple variables with the same key,
 use filter to select the correct environment_scope.",
@@ -48,6 +54,88 @@ use filter to select the correct environment_scope.",
     cli_long_help = "Delete a project's variable. If there are multiple variables with the same key,
 use filter to select the correct environment_scope.",
     cli_help = "Delete a project's variable."
+  ),
+  // Groups
+  endpoint(
+    route = "/groups/{id}/variables",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/groups/{id}/variables",
+    cli_help = "Get list of a group's variables."
+  ),
+  endpoint(
+    route = "/groups/{id}/variables/{var_key}",
+    extra_action = "output_result",
+    cli_route = "/groups/{id}/variables/{var_key}",
+    cli_help = "Get the details of a group's specific variable."
+  ),
+  endpoint(
+    route = "/groups/{id}/variables",
+    method = "POST",
+    payload_struct = "ProjectsVariableCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/groups/{id}/variables/create",
+    cli_help = "Create a new group variable."
+  ),
+  endpoint(
+    route = "/groups/{id}/variables/{var_key}",
+    method = "PUT",
+    payload_struct = "ProjectsVariableUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/groups/{id}/variables/{var_key}/update",
+    cli_help = "Update a group's variable."
+  ),
+  endpoint(
+    route = "/groups/{id}/variables/{var_key}",
+    method = "DELETE",
+    payload_struct = "ProjectsVariableDeletePayload",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/groups/{id}/variables/{var_key}/delete",
+    cli_help = "Delete a group's variable."
+  ),
+  // Instance
+  endpoint(
+    route = "/admin/ci/variables",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/admin/ci/variables",
+    cli_help = "Get the list of all instance-level variables."
+  ),
+  endpoint(
+    route = "/admin/ci/variables/{var_key}",
+    extra_action = "output_result",
+    cli_route = "/admin/ci/variables/{var_key}",
+    cli_help = "Get the details of a specific instance-level variable."
+  ),
+  endpoint(
+    route = "/admin/ci/variables",
+    method = "POST",
+    payload_struct = "ProjectsVariableCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/admin/ci/variables/create",
+    cli_long_help = "Create a new instance-level variable. Instance-level variables have no environment_scope.",
+    cli_help = "Create a new instance-level variable."
+  ),
+  endpoint(
+    route = "/admin/ci/variables/{var_key}",
+    method = "PUT",
+    payload_struct = "ProjectsVariableUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/admin/ci/variables/{var_key}/update",
+    cli_help = "Update an instance-level variable."
+  ),
+  endpoint(
+    route = "/admin/ci/variables/{var_key}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/admin/ci/variables/{var_key}/delete",
+    cli_help = "Delete an instance-level variable."
   )
 )]
 #[derive(PrettyPrint)] // skip_none  formatter bool
@@ -61,6 +149,8 @@ pub(crate) struct ProjectVariables {
   masked: bool,
   #[api(table_skip)]
   raw: bool,
+  /// Instance-level variables have no environment scope.
+  #[serde(default)]
   environment_scope: String,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[api(table_skip)]
@@ -0,0 +1,584 @@
+//! `variables export` and `variables import`: copy the variables of a project through a
+//! dotenv, JSON or YAML file.
+//!
+//! A variable is identified by its key and its environment scope. In the dotenv files,
+//! the attributes of a variable are given by a `# gitlab-cli:` comment before it:
+//!
+//! ```text
+//! # gitlab-cli: environment_scope="production" protected masked
+//! REGISTRY_TOKEN="glpat-..."
+//! ```
+
+use super::ProjectVariables;
+use crate::{ids::encode, session::Session};
+use clap::{ArgMatches, Command};
+use crud_api::ApiInput;
+use hyper::Method;
+use is_terminal::IsTerminal;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashSet,
+  fs::OpenOptions,
+  io::{stdin, stdout, Read, Write},
+};
+
+/// Variables read per request.
+const PER_PAGE: usize = 100;
+/// Prefix of the comments holding the attributes of the next variable.
+const ATTRIBUTES: &str = "# gitlab-cli:";
+const DEFAULT_VARIABLE_TYPE: &str = "env_var";
+const DEFAULT_SCOPE: &str = "*";
+
+/// Options of `variables export`. They are not sent to Gitlab.
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct VariablesExportQuery {
+  #[api(
+    no_short,
+    help = "File written with the variables (default: the standard output)."
+  )]
+  #[serde(skip)]
+  file: Option<String>,
+
+  #[api(
+    no_short,
+    long = "file-format",
+    help = "Format of the file (default: given by the extension of the file, or dotenv).",
+    possible_values = "dotenv,json,yaml"
+  )]
+  #[serde(skip)]
+  file_format: Option<String>,
+}
+
+/// Options of `variables import`. They are not sent to Gitlab.
+#[derive(ApiInput, Debug, Default, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct VariablesImportQuery {
+  #[api(
+    no_short,
+    help = "File of the variables, or `-` for the standard input.",
+    long_help = "File of the variables, as written by `variables export`, or `-` for the standard input."
+  )]
+  #[serde(skip)]
+  file: String,
+
+  #[api(
+    no_short,
+    long = "file-format",
+    help = "Format of the file (default: given by the extension of the file, or dotenv).",
+    possible_values = "dotenv,json,yaml"
+  )]
+  #[serde(skip)]
+  file_format: Option<String>,
+
+  #[api(
+    no_short,
+    help = "Delete the variables of the project that are not in the file."
+  )]
+  #[serde(skip)]
+  prune: Option<bool>,
+
+  #[api(
+    no_short,
+    help = "Apply the changes without asking for a confirmation.",
+    long_help = "Apply the changes without asking for a confirmation. Without a terminal, the changes are only shown unless this option is given."
+  )]
+  #[serde(skip)]
+  yes: Option<bool>,
+}
+
+/// Declarations of `variables export` and `variables import`.
+pub(crate) fn commands() -> [Command; 2] {
+  [
+    VariablesExportQuery::clap(
+      Command::new("export")
+        .about("Export the variables of a project to a dotenv, JSON or YAML file.")
+        .long_about("Export all the variables of a project, with their environment scopes and flags, to a dotenv, JSON or YAML file. The file can be applied to another project with `variables import`."),
+      None,
+    ),
+    VariablesImportQuery::clap(
+      Command::new("import")
+        .about("Import the variables of a dotenv, JSON or YAML file into a project.")
+        .long_about("Apply a file written by `variables export` to the variables of a project. A variable is identified by its key and its environment scope. The variables to create, update and delete are shown first. They are applied once confirmed, or with `--yes`."),
+      None,
+    ),
+  ]
+}
+
+/// Variable as written in the files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Variable {
+  key: String,
+  value: String,
+  #[serde(default = "default_variable_type")]
+  variable_type: String,
+  #[serde(default = "default_scope")]
+  environment_scope: String,
+  #[serde(default)]
+  protected: bool,
+  #[serde(default)]
+  masked: bool,
+  #[serde(default)]
+  raw: bool,
+  /// When it is omitted from a file, the description of the project's variable is kept.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+}
+
+fn default_variable_type() -> String {
+  DEFAULT_VARIABLE_TYPE.to_string()
+}
+
+fn default_scope() -> String {
+  DEFAULT_SCOPE.to_string()
+}
+
+impl Variable {
+  fn new(key: &str, value: String) -> Self {
+    Variable {
+      key: key.to_string(),
+      value,
+      variable_type: default_variable_type(),
+      environment_scope: default_scope(),
+      protected: false,
+      masked: false,
+      raw: false,
+      description: None,
+    }
+  }
+
+  fn same_variable(&self, other: &Variable) -> bool {
+    self.key == other.key && self.environment_scope == other.environment_scope
+  }
+
+  /// Attributes of the `current` variable changed by this one.
+  fn changes(&self, current: &Variable) -> Vec<&'static str> {
+    [
+      ("value", self.value != current.value),
+      ("variable_type", self.variable_type != current.variable_type),
+      ("protected", self.protected != current.protected),
+      ("masked", self.masked != current.masked),
+      ("raw", self.raw != current.raw),
+      (
+        "description",
+        self.description.is_some() && self.description != current.description,
+      ),
+    ]
+    .into_iter()
+    .filter_map(|(name, changed)| changed.then_some(name))
+    .collect()
+  }
+
+  /// Route of the variable, with the filter selecting its environment scope.
+  fn route(&self, id: &str) -> String {
+    format!(
+      "/projects/{id}/variables/{}?filter%5Benvironment_scope%5D={}",
+      encode(&self.key),
+      encode(&self.environment_scope)
+    )
+  }
+}
+
+impl From<ProjectVariables> for Variable {
+  fn from(variable: ProjectVariables) -> Self {
+    Variable {
+      key: variable.key,
+      value: variable.value,
+      variable_type: variable.variable_type.unwrap_or_else(default_variable_type),
+      environment_scope: variable.environment_scope,
+      protected: variable.protected,
+      masked: variable.masked,
+      raw: variable.raw,
+      description: variable.description,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+  Dotenv,
+  Json,
+  Yaml,
+}
+
+impl Format {
+  /// Format given by `--file-format`, or else by the extension of the `file`.
+  fn new(format: Option<&str>, file: Option<&str>) -> Self {
+    let format = format.or_else(|| file?.rsplit_once('.').map(|(_, extension)| extension));
+    match format {
+      Some("json") => Format::Json,
+      Some("yaml" | "yml") => Format::Yaml,
+      _ => Format::Dotenv,
+    }
+  }
+
+  fn write(self, variables: &[Variable]) -> Result<String> {
+    match self {
+      Format::Dotenv => Ok(write_dotenv(variables)),
+      Format::Json => serde_json::to_string_pretty(variables)
+        .map(|json| json + "\n")
+        .into_diagnostic(),
+      Format::Yaml => serde_yaml::to_string(variables).into_diagnostic(),
+    }
+  }
+
+  fn read(self, content: &str) -> Result<Vec<Variable>> {
+    match self {
+      Format::Dotenv => read_dotenv(content),
+      Format::Json => serde_json::from_str(content).into_diagnostic(),
+      Format::Yaml => serde_yaml::from_str(content).into_diagnostic(),
+    }
+  }
+}
+
+/// Writes the variables of the project `id` to the file given by `--file`.
+pub(crate) async fn export(session: &Session, matches: &ArgMatches) -> Result<i32> {
+  let id = session.arg("id").unwrap_or_default();
+  let options = VariablesExportQuery::from_clap_matches(matches)?;
+  let file = options.file.as_deref().filter(|file| *file != "-");
+  let format = Format::new(options.file_format.as_deref(), file);
+
+  let content = format.write(&fetch(session, &id).await?)?;
+  match file {
+    Some(file) => write_private(file, &content)
+      .into_diagnostic()
+      .wrap_err_with(|| format!("Can't write {file}"))?,
+    None => print!("{content}"),
+  }
+  Ok(0)
+}
+
+/// Writes the `content` to the `file`, readable by its owner only: it holds the values of
+/// the variables.
+fn write_private(file: &str, content: &str) -> std::io::Result<()> {
+  let mut options = OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::OpenOptionsExt;
+    options.mode(0o600);
+  }
+  let mut out = options.open(file)?;
+  // An existing file keeps its mode when it is opened.
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    out.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+  }
+  out.write_all(content.as_bytes())
+}
+
+/// Shows the changes made by the file given by `--file` to the variables of the
+/// project `id`, then applies them once they are confirmed.
+pub(crate) async fn import(session: &Session, matches: &ArgMatches) -> Result<i32> {
+  let id = session.arg("id").unwrap_or_default();
+  let options = VariablesImportQuery::from_clap_matches(matches)?;
+  let from_stdin = options.file == "-";
+  let format = Format::new(
+    options.file_format.as_deref(),
+    Some(options.file.as_str()).filter(|_| !from_stdin),
+  );
+  let content = if from_stdin {
+    let mut content = String::new();
+    stdin()
+      .read_to_string(&mut content)
+      .into_diagnostic()
+      .wrap_err("Can't read the standard input")?;
+    content
+  } else {
+    std::fs::read_to_string(&options.file)
+      .into_diagnostic()
+      .wrap_err_with(|| format!("Can't read {}", options.file))?
+  };
+  let variables = format
+    .read(&content)
+    .wrap_err_with(|| format!("Can't read the variables of {}", options.file))?;
+  check_duplicates(&variables)?;
+
+  let current = fetch(session, &id).await?;
+  let plan = Plan::new(variables, &current, options.prune.unwrap_or(false));
+  print!("{plan}");
+  if plan.is_empty() {
+    return Ok(0);
+  }
+  if !options.yes.unwrap_or(false) {
+    if from_stdin || !stdin().is_terminal() {
+      println!("Dry run: run the command again with --yes to apply the changes.");
+      return Ok(0);
+    }
+    if !confirm()? {
+      return Ok(1);
+    }
+  }
+  plan.apply(session, &id).await?;
+  Ok(0)
+}
+
+/// All the variables of the project `id`.
+async fn fetch(session: &Session, id: &str) -> Result<Vec<Variable>> {
+  let mut variables = vec![];
+  for page in 1.. {
+    let variables_page: Vec<ProjectVariables> = session
+      .get(&format!(
+        "/projects/{id}/variables?per_page={PER_PAGE}&page={page}"
+      ))
+      .await?;
+    let last = variables_page.len() < PER_PAGE;
+    variables.extend(variables_page.into_iter().map(Variable::from));
+    if last {
+      break;
+    }
+  }
+  variables.sort_by(|a, b| (&a.key, &a.environment_scope).cmp(&(&b.key, &b.environment_scope)));
+  Ok(variables)
+}
+
+fn check_duplicates(variables: &[Variable]) -> Result<()> {
+  let mut seen = HashSet::new();
+  for variable in variables {
+    if !seen.insert((&variable.key, &variable.environment_scope)) {
+      return Err(miette!(
+        "The variable {} is declared twice for the environment scope {}.",
+        variable.key,
+        variable.environment_scope
+      ));
+    }
+  }
+  Ok(())
+}
+
+fn confirm() -> Result<bool> {
+  print!("Apply these changes? [y/N] ");
+  stdout().flush().into_diagnostic()?;
+  let mut answer = String::new();
+  stdin().read_line(&mut answer).into_diagnostic()?;
+  Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Changes made by a file to the variables of a project.
+#[derive(Debug, Default)]
+struct Plan {
+  creates: Vec<Variable>,
+  updates: Vec<(Variable, Vec<&'static str>)>,
+  deletes: Vec<Variable>,
+}
+
+impl Plan {
+  fn new(variables: Vec<Variable>, current: &[Variable], prune: bool) -> Self {
+    let mut plan = Plan::default();
+    if prune {
+      plan.deletes = current
+        .iter()
+        .filter(|old| !variables.iter().any(|new| new.same_variable(old)))
+        .cloned()
+        .collect();
+    }
+    for variable in variables {
+      match current.iter().find(|old| old.same_variable(&variable)) {
+        None => plan.creates.push(variable),
+        Some(old) => {
+          let changes = variable.changes(old);
+          if !changes.is_empty() {
+            plan.updates.push((variable, changes));
+          }
+        }
+      }
+    }
+    plan
+  }
+
+  fn is_empty(&self) -> bool {
+    self.creates.is_empty() && self.updates.is_empty() && self.deletes.is_empty()
+  }
+
+  async fn apply(&self, session: &Session, id: &str) -> Result<()> {
+    for variable in &self.creates {
+      session
+        .send_json(
+          Method::POST,
+          &format!("/projects/{id}/variables"),
+          Some(variable),
+        )
+        .await
+        .wrap_err_with(|| format!("Can't create the variable {}", variable.key))?;
+    }
+    for (variable, _) in &self.updates {
+      session
+        .send_json(Method::PUT, &variable.route(id), Some(variable))
+        .await
+        .wrap_err_with(|| format!("Can't update the variable {}", variable.key))?;
+    }
+    for variable in &self.deletes {
+      session
+        .send_json(Method::DELETE, &variable.route(id), None::<&Variable>)
+        .await
+        .wrap_err_with(|| format!("Can't delete the variable {}", variable.key))?;
+    }
+    println!(
+      "{} created, {} updated, {} deleted.",
+      self.creates.len(),
+      self.updates.len(),
+      self.deletes.len()
+    );
+    Ok(())
+  }
+}
+
+/// The values are not shown: they are often secrets.
+impl std::fmt::Display for Plan {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.is_empty() {
+      return writeln!(f, "The variables are up to date.");
+    }
+    for variable in &self.creates {
+      writeln!(f, "+ {} ({})", variable.key, variable.environment_scope)?;
+    }
+    for (variable, changes) in &self.updates {
+      writeln!(
+        f,
+        "~ {} ({}): {}",
+        variable.key,
+        variable.environment_scope,
+        changes.join(", ")
+      )?;
+    }
+    for variable in &self.deletes {
+      writeln!(f, "- {} ({})", variable.key, variable.environment_scope)?;
+    }
+    writeln!(
+      f,
+      "{} to create, {} to update, {} to delete.",
+      self.creates.len(),
+      self.updates.len(),
+      self.deletes.len()
+    )
+  }
+}
+
+fn write_dotenv(variables: &[Variable]) -> String {
+  let mut content = String::new();
+  for variable in variables {
+    let mut attributes = vec![];
+    if variable.variable_type != DEFAULT_VARIABLE_TYPE {
+      attributes.push(format!("variable_type={}", quote(&variable.variable_type)));
+    }
+    if variable.environment_scope != DEFAULT_SCOPE {
+      attributes.push(format!(
+        "environment_scope={}",
+        quote(&variable.environment_scope)
+      ));
+    }
+    for (flag, set) in [
+      ("protected", variable.protected),
+      ("masked", variable.masked),
+      ("raw", variable.raw),
+    ] {
+      if set {
+        attributes.push(flag.to_string());
+      }
+    }
+    if let Some(description) = &variable.description {
+      attributes.push(format!("description={}", quote(description)));
+    }
+    if !attributes.is_empty() {
+      content.push_str(&format!("{ATTRIBUTES} {}\n", attributes.join(" ")));
+    }
+    content.push_str(&format!("{}={}\n", variable.key, quote(&variable.value)));
+  }
+  content
+}
+
+fn read_dotenv(content: &str) -> Result<Vec<Variable>> {
+  let mut variables = vec![];
+  let mut attributes: Option<Vec<(String, Option<String>)>> = None;
+  for (number, line) in content.lines().enumerate() {
+    let at_line = || format!("Line {}: {line}", number + 1);
+    let line = line.trim();
+    if let Some(rest) = line.strip_prefix(ATTRIBUTES) {
+      attributes = Some(parse_attributes(rest).wrap_err_with(at_line)?);
+      continue;
+    }
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line
+      .split_once('=')
+      .ok_or_else(|| miette!("Expected KEY=value"))
+      .wrap_err_with(at_line)?;
+    let mut variable = Variable::new(
+      key.trim(),
+      parse_value(value.trim()).wrap_err_with(at_line)?,
+    );
+    for (name, value) in attributes.take().unwrap_or_default() {
+      match (name.as_str(), value) {
+        ("variable_type", Some(value)) => variable.variable_type = value,
+        ("environment_scope", Some(value)) => variable.environment_scope = value,
+        ("description", value) => variable.description = Some(value.unwrap_or_default()),
+        ("protected", None) => variable.protected = true,
+        ("masked", None) => variable.masked = true,
+        ("raw", None) => variable.raw = true,
+        (name, _) => return Err(miette!("Unknown attribute {name}")).wrap_err_with(at_line),
+      }
+    }
+    variables.push(variable);
+  }
+  Ok(variables)
+}
+
+/// Parses the attributes of a variable: `environment_scope="production" protected`.
+fn parse_attributes(mut line: &str) -> Result<Vec<(String, Option<String>)>> {
+  let mut attributes = vec![];
+  loop {
+    line = line.trim_start();
+    if line.is_empty() {
+      return Ok(attributes);
+    }
+    let end = line
+      .find(|c: char| c == '=' || c.is_whitespace())
+      .unwrap_or(line.len());
+    let name = line[..end].to_string();
+    line = &line[end..];
+    match line.strip_prefix('=') {
+      Some(rest) => {
+        let (value, rest) = parse_string(rest)?;
+        attributes.push((name, Some(value)));
+        line = rest;
+      }
+      None => attributes.push((name, None)),
+    }
+  }
+}
+
+/// Value of a variable: `"quoted"` with escapes, `'quoted'` without escapes, or bare.
+fn parse_value(value: &str) -> Result<String> {
+  if value.starts_with('"') {
+    let (value, rest) = parse_string(value)?;
+    if !rest.trim().is_empty() && !rest.trim_start().starts_with('#') {
+      return Err(miette!("Unexpected characters after the value: {rest}"));
+    }
+    Ok(value)
+  } else if let Some(quoted) = value.strip_prefix('\'') {
+    quoted
+      .rsplit_once('\'')
+      .map(|(value, _)| value.to_string())
+      .ok_or_else(|| miette!("Missing closing quote"))
+  } else {
+    Ok(value.to_string())
+  }
+}
+
+/// Parses the double quoted string at the start of `value`, and returns the rest.
+fn parse_string(value: &str) -> Result<(String, &str)> {
+  let mut strings = serde_json::Deserializer::from_str(value).into_iter::<String>();
+  let string = strings
+    .next()
+    .ok_or_else(|| miette!("Expected a quoted string"))?
+    .into_diagnostic()?;
+  Ok((string, &value[strings.byte_offset()..]))
+}
+
+fn quote(value: &str) -> String {
+  serde_json::Value::String(value.to_string()).to_string()
+}
@@ -0,0 +1,279 @@
+use crud_api::{Api, ApiInput};
+use serde::{Deserialize, Serialize};
+
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/protected_branches",
+    query_struct = "ProtectedBranchQuery",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/protected_branches",
+    cli_help = "Get a list of the protected branches of a project.",
+    cli_long_help = "Get a list of the protected branches of a project. Their names may contain `*` wildcards, protecting all the matching branches."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_branches/{name}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/protected_branches/{name}",
+    cli_help = "Get a single protected branch or wildcard protected branch."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_branches",
+    method = "POST",
+    payload_struct = "ProtectedBranchCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/protected_branches/protect",
+    cli_help = "Protect a single repository branch or several project repository branches using a wildcard protected branch."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_branches/{name}",
+    method = "PATCH",
+    payload_struct = "ProtectedBranchUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/protected_branches/{name}/update",
+    cli_help = "Update a protected branch: allow the force push or require the approval of the code owners."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_branches/{name}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/protected_branches/{name}/unprotect",
+    cli_help = "Unprotect the given protected branch or wildcard protected branch."
+  )
+)]
+pub(crate) struct ProtectedBranch {
+  id: u32,
+  pub(crate) name: String,
+  #[api(table_skip)]
+  pub(crate) push_access_levels: Vec<ProtectedAccessLevel>,
+  #[api(table_skip)]
+  pub(crate) merge_access_levels: Vec<ProtectedAccessLevel>,
+  /// Only given by the premium and ultimate tiers.
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) unprotect_access_levels: Option<Vec<ProtectedAccessLevel>>,
+  pub(crate) allow_force_push: bool,
+  /// Only given by the premium and ultimate tiers.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) code_owner_approval_required: Option<bool>,
+}
+
+/// A role, a user, a group or a deploy key allowed to push, merge, unprotect or create.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub(crate) struct ProtectedAccessLevel {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  id: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  access_level: Option<u32>,
+  pub(crate) access_level_description: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_id: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  group_id: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  deploy_key_id: Option<u32>,
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/protected_tags",
+    query_struct = "ProtectedTagQuery",
+    multiple_results,
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/protected_tags",
+    cli_help = "Get a list of the protected tags of a project."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_tags/{name}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/protected_tags/{name}",
+    cli_help = "Get a single protected tag or wildcard protected tag."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_tags",
+    method = "POST",
+    payload_struct = "ProtectedTagCreatePayload",
+    result_ok_status = "CREATED",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/protected_tags/protect",
+    cli_help = "Protect a single repository tag or several project repository tags using a wildcard protected tag."
+  ),
+  endpoint(
+    route = "/projects/{id}/protected_tags/{name}",
+    method = "DELETE",
+    result_ok_status = "NO_CONTENT",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/protected_tags/{name}/unprotect",
+    cli_help = "Unprotect the given protected tag or wildcard protected tag."
+  )
+)]
+pub(crate) struct ProtectedTag {
+  name: String,
+  #[api(table_skip)]
+  create_access_levels: Vec<ProtectedAccessLevel>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug)]
+#[api(no_input_file)]
+pub(crate) struct ProtectedBranchQuery {
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "Name or part of the name of the protected branches to be searched for."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  search: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  per_page: Option<u32>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug)]
+#[api(no_input_file)]
+pub(crate) struct ProtectedTagQuery {
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  per_page: Option<u32>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
+#[api(no_input_file)]
+pub(crate) struct ProtectedBranchCreatePayload {
+  #[api(
+    no_short,
+    help = "The name of the branch or wildcard.",
+    long_help = "The name of the branch or wildcard: `release/*` protects all the branches starting with `release/`."
+  )]
+  name: String,
+
+  /// The access levels are strings: `possible_values` only parses strings. Gitlab
+  /// parses them as integers.
+  #[api(
+    no_short,
+    long = "push-access-level",
+    help = "Access levels allowed to push (default: 40, maintainers).",
+    long_help = "Access levels allowed to push: 0 (no one), 30 (developers and maintainers), 40 (maintainers) or 60 (administrators). Default: 40.",
+    possible_values = "0,30,40,60"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  push_access_level: Option<String>,
+
+  #[api(
+    no_short,
+    long = "merge-access-level",
+    help = "Access levels allowed to merge (default: 40, maintainers).",
+    long_help = "Access levels allowed to merge: 0 (no one), 30 (developers and maintainers), 40 (maintainers) or 60 (administrators). Default: 40.",
+    possible_values = "0,30,40,60"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  merge_access_level: Option<String>,
+
+  #[api(
+    no_short,
+    long = "unprotect-access-level",
+    help = "Access levels allowed to unprotect (default: 40, maintainers).",
+    long_help = "Access levels allowed to unprotect: 0 (no one), 30 (developers and maintainers), 40 (maintainers) or 60 (administrators). Default: 40. Premium and ultimate tiers only.",
+    possible_values = "0,30,40,60"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  unprotect_access_level: Option<String>,
+
+  #[api(
+    no_short,
+    long = "allow-force-push",
+    help = "Allow all users with push access to force push."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  allow_force_push: Option<bool>,
+
+  #[api(
+    no_short,
+    long = "code-owner-approval-required",
+    help = "Prevent pushes to this branch if it matches an item in the CODEOWNERS file.",
+    long_help = "Prevent pushes to this branch if it matches an item in the CODEOWNERS file. Premium and ultimate tiers only."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  code_owner_approval_required: Option<bool>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
+#[api(no_input_file)]
+pub(crate) struct ProtectedBranchUpdatePayload {
+  /// A string: a flag could not disallow the force push. Gitlab parses it as a boolean.
+  #[api(
+    no_short,
+    long = "allow-force-push",
+    help = "Allow (`true`) or disallow (`false`) the force push to the branch.",
+    possible_values = "true,false"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  allow_force_push: Option<String>,
+
+  #[api(
+    no_short,
+    long = "code-owner-approval-required",
+    help = "Require (`true`) or not (`false`) the approval of the code owners.",
+    long_help = "Require (`true`) or not (`false`) the approval of the code owners to push to the branch. Premium and ultimate tiers only.",
+    possible_values = "true,false"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  code_owner_approval_required: Option<String>,
+}
+
+#[derive(ApiInput, Serialize, Deserialize, Debug, Default)]
+#[api(no_input_file)]
+pub(crate) struct ProtectedTagCreatePayload {
+  #[api(
+    no_short,
+    help = "The name of the tag or wildcard.",
+    long_help = "The name of the tag or wildcard: `v*` protects all the tags starting with `v`."
+  )]
+  name: String,
+
+  #[api(
+    no_short,
+    long = "create-access-level",
+    help = "Access levels allowed to create (default: 40, maintainers).",
+    long_help = "Access levels allowed to create: 0 (no one), 30 (developers and maintainers), 40 (maintainers) or 60 (administrators). Default: 40.",
+    possible_values = "0,30,40,60"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  create_access_level: Option<String>,
+}
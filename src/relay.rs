@@ -0,0 +1,128 @@
+//! Relay of the requests of the generated commands.
+//!
+//! crud-api sends the requests of the generated commands itself, on a new connection and
+//! without retry. Its base URL is a relay listening on the loopback interface: the relay
+//! sends the requests to Gitlab with the [Session], so they are retried and time out like
+//! the requests of the CLI itself, see [crate::retry].
+
+use crate::{commands, profiles, session::Session};
+use clap::{
+  builder::{StringValueParser, TypedValueParser},
+  Command,
+};
+use http_body_util::{BodyExt, Full};
+use hyper::{
+  body::{Bytes, Incoming},
+  header::{CONNECTION, CONTENT_LENGTH, CONTENT_TYPE, TRANSFER_ENCODING},
+  server::conn::http1,
+  service::service_fn,
+  Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use miette::{IntoDiagnostic, Result, WrapErr};
+use std::{convert::Infallible, sync::OnceLock};
+use tokio::net::TcpListener;
+
+/// URL of the relay, and base URL of the API the requests are sent to.
+struct Relay {
+  url: String,
+  upstream: String,
+}
+
+static RELAY: OnceLock<Relay> = OnceLock::new();
+
+/// Starts the relay of the generated command given by the arguments.
+///
+/// Nothing is started when the arguments are not valid: the generated dispatch reports them.
+pub(crate) async fn start() -> Result<()> {
+  let Some(matches) = commands::pre_parse() else {
+    return Ok(());
+  };
+  let settings = profiles::settings()?;
+  let upstream = Session::base_url(&matches, &settings);
+  let listener = TcpListener::bind("127.0.0.1:0")
+    .await
+    .into_diagnostic()
+    .wrap_err("Can't start the relay of the requests")?;
+  let url = format!("http://{}", listener.local_addr().into_diagnostic()?);
+  let _ = RELAY.set(Relay { url, upstream });
+  tokio::spawn(async move {
+    while let Ok((stream, _)) = listener.accept().await {
+      tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(stream), service_fn(forward)));
+    }
+  });
+  Ok(())
+}
+
+/// Base URL of the API when the relay is started: the `base_url` argument is the relay.
+pub(crate) fn upstream() -> Option<&'static str> {
+  RELAY.get().map(|relay| relay.upstream.as_str())
+}
+
+/// Makes the generated commands send their requests to the relay, whatever the base URL.
+pub(crate) fn apply(command: Command) -> Command {
+  match RELAY.get() {
+    Some(relay) => command.mut_arg("base_url", |arg| {
+      arg
+        .default_value(relay.url.as_str())
+        .hide_default_value(true)
+        .value_parser(StringValueParser::new().map(|_| relay.url.clone()))
+    }),
+    None => command,
+  }
+}
+
+/// Sends the request to the API. The errors are answered as a `502` describing them.
+async fn forward(request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+  let response = match send(request).await {
+    Ok(response) => response,
+    Err(err) => {
+      let message = err.chain().map(ToString::to_string).collect::<Vec<_>>();
+      Response::builder()
+        .status(StatusCode::BAD_GATEWAY)
+        .body(Full::new(Bytes::from(message.join(": "))))
+        .unwrap_or_default()
+    }
+  };
+  Ok(response)
+}
+
+async fn send(request: Request<Incoming>) -> Result<Response<Full<Bytes>>> {
+  let (parts, body) = request.into_parts();
+  let body = body
+    .collect()
+    .await
+    .into_diagnostic()
+    .wrap_err("Can't read the request")?
+    .to_bytes();
+  let body = (!body.is_empty())
+    .then(|| String::from_utf8(body.to_vec()))
+    .transpose()
+    .into_diagnostic()?;
+  let route = parts
+    .uri
+    .path_and_query()
+    .map_or(parts.uri.path(), |path| path.as_str());
+  // The authentication is added by the session.
+  let headers: Vec<_> = parts
+    .headers
+    .get(CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .map(|value| (CONTENT_TYPE, value.to_string()))
+    .into_iter()
+    .collect();
+  let response = Session::current()?
+    .send_raw(parts.method, route, &headers, body)
+    .await?;
+
+  let mut builder = Response::builder().status(response.status);
+  for (name, value) in &response.headers {
+    // The body is sent whole, with its own length.
+    if ![CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING].contains(name) {
+      builder = builder.header(name, value);
+    }
+  }
+  builder
+    .body(Full::new(Bytes::from(response.body)))
+    .into_diagnostic()
+}
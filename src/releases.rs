@@ -0,0 +1,443 @@
+use crate::{
+  commit::Commit,
+  milestones::Milestone,
+  repository::changelog::{Changelog, ChangelogPayload},
+  session::Session,
+  user::User,
+};
+use clap::{ArgMatches, Command};
+use crud_api::{Api, ApiInput};
+use hyper::Method;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/releases",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "ReleaseFilter",
+    cli_route = "/projects/{id}/releases",
+    cli_help = "Paginated list of releases, sorted by `released_at`.",
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}",
+    cli_help = "Get a release for the given tag.",
+  ),
+  endpoint(
+    route = "/projects/{id}/releases",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "ReleaseCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/create",
+    cli_help = "Creates a release.",
+    cli_long_help = "Creates a release. Developer level access to the project is required to create a release."
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}",
+    method = "PUT",
+    payload_struct = "ReleaseUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}/edit",
+    cli_help = "Updates a release.",
+    cli_long_help = "Updates a release. Developer level access to the project is required to update a release."
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}",
+    method = "DELETE",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}/delete",
+    cli_help = "Deletes a release.",
+    cli_long_help = "Deletes a release. Deleting a release doesn't delete the associated tag. Maintainer level access to the project is required to delete a release."
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}/evidence",
+    method = "POST",
+    result_ok_status = "ACCEPTED",
+    result_struct = "EmptyResponse",
+    cli_no_output,
+    cli_route = "/projects/{id}/releases/{tag_name}/evidence",
+    cli_help = "Creates an evidence for an existing release.",
+  )
+)]
+pub(crate) struct Release {
+  tag_name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  #[api(table_format(date(format = "%Y-%m-%d %H:%M:%S")))]
+  created_at: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  released_at: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  upcoming_release: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  author: Option<User>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  commit: Option<Commit>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  milestones: Option<Vec<Milestone>>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  commit_path: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tag_path: Option<String>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  assets: Option<Assets>,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  evidences: Option<Vec<Evidence>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct Assets {
+  count: u32,
+  sources: Vec<Source>,
+  links: Vec<ReleaseLink>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct Source {
+  format: String,
+  url: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub(crate) struct Evidence {
+  sha: String,
+  filepath: String,
+  collected_at: String,
+}
+
+#[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
+#[api(
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}/assets/links",
+    multiple_results,
+    extra_action = "output_rows",
+    query_struct = "ReleaseLinkFilter",
+    cli_route = "/projects/{id}/releases/{tag_name}/assets/links",
+    cli_help = "Get assets as links from a release.",
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}/assets/links/{link_id}",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}/assets/links/{link_id}",
+    cli_help = "Get an asset as a link from a release.",
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}/assets/links",
+    method = "POST",
+    result_ok_status = "CREATED",
+    payload_struct = "ReleaseLinkCreatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}/assets/links/create",
+    cli_help = "Creates an asset as a link from a release.",
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}/assets/links/{link_id}",
+    method = "PUT",
+    payload_struct = "ReleaseLinkUpdatePayload",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}/assets/links/{link_id}/edit",
+    cli_help = "Updates an asset as a link from a release.",
+  ),
+  endpoint(
+    route = "/projects/{id}/releases/{tag_name}/assets/links/{link_id}",
+    method = "DELETE",
+    extra_action = "output_result",
+    cli_route = "/projects/{id}/releases/{tag_name}/assets/links/{link_id}/delete",
+    cli_help = "Deletes an asset as a link from a release.",
+  )
+)]
+pub(crate) struct ReleaseLink {
+  id: u32,
+  name: String,
+  url: String,
+  #[api(table_skip)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  direct_asset_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  link_type: Option<String>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct ReleaseFilter {
+  #[api(
+    no_short,
+    long = "order-by",
+    heading = "Sorting",
+    help = "The field to use as order.",
+    long_help = "The field to use as order. Either `released_at` (default) or `created_at`.",
+    possible_values = "released_at,created_at"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  order_by: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Sorting",
+    help = "The direction of the order.",
+    long_help = "The direction of the order. Either `desc` (default) for descending order or `asc` for ascending order.",
+    possible_values = "asc,desc"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sort: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct ReleaseLinkFilter {
+  #[api(
+    no_short,
+    heading = "Pagination",
+    help = "Page number.",
+    long_help = "Page number (default: 1)."
+  )]
+  page: Option<u32>,
+
+  #[api(
+    no_short,
+    long = "per-page",
+    heading = "Pagination",
+    help = "Number of items to list per page.",
+    long_help = "Number of items to list per page (default: 20, max: 100)."
+  )]
+  per_page: Option<u32>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ReleaseCreatePayload {
+  #[api(
+    no_short,
+    long = "tag-name",
+    help = "The tag where the release is created from."
+  )]
+  tag_name: String,
+
+  #[api(
+    no_short,
+    long = "tag-message",
+    help = "Message to use if creating a new annotated tag."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tag_message: Option<String>,
+
+  #[api(no_short, help = "The release name.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The description of the release.",
+    long_help = "The description of the release. You can use Markdown."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+
+  #[api(
+    no_short,
+    long = "ref",
+    help = "The commit SHA, another tag name, or a branch name.",
+    long_help = "If a tag specified in `tag_name` doesn't exist, the release is created from `ref` and tagged with `tag_name`. It can be a commit SHA, another tag name, or a branch name."
+  )]
+  #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+  _ref: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The title of each milestone the release is associated with."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  milestones: Option<Vec<String>>,
+
+  #[api(
+    no_short,
+    long = "released-at",
+    help = "Date and time for the release.",
+    long_help = "Date and time for the release. Defaults to the current time. Expected in ISO 8601 format (2019-03-15T08:00:00Z). Only provide this field if creating an upcoming or historical release."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  released_at: Option<String>,
+}
+
+/// Declaration of `releases create_from_changelog`, run by `main`: the release notes are
+/// generated before the release is created.
+pub(crate) fn create_from_changelog_command() -> Command {
+  let command = ReleaseCreatePayload::clap(
+    Command::new("create_from_changelog")
+      .about("Creates a release with the changelog as description.")
+      .long_about("Creates a release with the changelog as description. The release notes are generated by `repository changelog` for the changelog options, then the release is created. No release is created when the changelog is empty."),
+    None,
+  )
+  .mut_arg("description", |arg| {
+    arg
+      .help("Text put before the changelog in the description of the release.")
+      .long_help("Text put before the changelog in the description of the release. You can use Markdown.")
+  });
+  ChangelogPayload::clap(command.next_help_heading("Changelog"), None)
+}
+
+/// Generates the release notes of the changelog options, then creates the release of the
+/// project `id` with them.
+pub(crate) async fn create_from_changelog(session: &Session, matches: &ArgMatches) -> Result<i32> {
+  let id = session.arg("id").unwrap_or_default();
+  let range = ChangelogPayload::from_clap_matches(matches)?;
+  let changelog: Changelog = session
+    .get_with_query(
+      &format!("/projects/{id}/repository/changelog"),
+      Some(&range),
+    )
+    .await
+    .wrap_err("Can't generate the changelog.")?;
+  let notes = changelog.notes();
+  if notes.trim().is_empty() {
+    return Err(miette!(
+      "The changelog of the version {} is empty: no release is created.",
+      range.version()
+    ));
+  }
+
+  let mut release = ReleaseCreatePayload::from_clap_matches(matches)?;
+  release.description = Some(match release.description.take() {
+    Some(description) => format!("{description}\n\n{notes}"),
+    None => notes,
+  });
+  let response = session
+    .send_json(
+      Method::POST,
+      &format!("/projects/{id}/releases"),
+      Some(&release),
+    )
+    .await
+    .wrap_err_with(|| format!("Can't create the release {}", release.tag_name))?;
+  let created: Release = serde_json::from_slice(&response.body)
+    .into_diagnostic()
+    .wrap_err("Can't deserialize the response")?;
+  println!("Created the release {}.", created.tag_name);
+  Ok(0)
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ReleaseUpdatePayload {
+  #[api(no_short, help = "The release name.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The description of the release.",
+    long_help = "The description of the release. You can use Markdown."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+
+  #[api(
+    no_short,
+    help = "The title of each milestone to associate with the release.",
+    long_help = "The title of each milestone to associate with the release. GitLab Premium customers can specify group milestones."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  milestones: Option<Vec<String>>,
+
+  #[api(
+    no_short,
+    long = "released-at",
+    help = "The date when the release is/was ready.",
+    long_help = "The date when the release is/was ready. Expected in ISO 8601 format (2019-03-15T08:00:00Z)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  released_at: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ReleaseLinkCreatePayload {
+  #[api(
+    no_short,
+    help = "The name of the link.",
+    long_help = "The name of the link. Link names must be unique in the release."
+  )]
+  name: String,
+
+  #[api(
+    no_short,
+    help = "The URL of the link.",
+    long_help = "The URL of the link. Link URLs must be unique in the release."
+  )]
+  url: String,
+
+  #[api(
+    no_short,
+    long = "direct-asset-path",
+    help = "Optional path for a direct asset link."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  direct_asset_path: Option<String>,
+
+  #[api(
+    no_short,
+    long = "link-type",
+    help = "The type of the link.",
+    long_help = "The type of the link: `other`, `runbook`, `image`, `package`. Defaults to `other`.",
+    possible_values = "other,runbook,image,package"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  link_type: Option<String>,
+}
+
+#[derive(Debug, Default, ApiInput, Serialize, Deserialize)]
+pub(crate) struct ReleaseLinkUpdatePayload {
+  #[api(no_short, help = "The name of the link.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+
+  #[api(no_short, help = "The URL of the link.")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  url: Option<String>,
+
+  #[api(
+    no_short,
+    long = "direct-asset-path",
+    help = "Optional path for a direct asset link."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  direct_asset_path: Option<String>,
+
+  #[api(
+    no_short,
+    long = "link-type",
+    help = "The type of the link.",
+    possible_values = "other,runbook,image,package"
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  link_type: Option<String>,
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[api(
   endpoint(
     route = "/projects/{id}/repository/blobs/{sha}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/blobs/{sha}",
     cli_help = "Allows you to receive information about blob in repository like size and content.",
     cli_long_help = "Allows you to receive information about blob in repository like size and content. Blob content is Base64 encoded.",
@@ -21,7 +21,16 @@ use serde::{Deserialize, Serialize};
     cli_help = "Generate changelog data based on commits in a repository, without committing them to a changelog file.",
   )
 )]
-pub(crate) struct Changelog {}
+pub(crate) struct Changelog {
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  notes: String,
+}
+
+impl Changelog {
+  pub(crate) fn notes(self) -> String {
+    self.notes
+  }
+}
 
 #[derive(Debug, ApiInput, Serialize, Deserialize)]
 #[api(no_input_file)]
@@ -89,3 +98,9 @@ pub(crate) struct ChangelogPayload {
   #[serde(skip_serializing_if = "Option::is_none")]
   message: Option<String>,
 }
+
+impl ChangelogPayload {
+  pub(crate) fn version(&self) -> &str {
+    &self.version
+  }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/projects/{id}/repository/compare",
   query_struct = "CompareQuery",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/repository/compare",
   cli_help = "This endpoint can be accessed without authentication if the repository is
 publicly accessible.",
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/projects/{id}/repository/contributors",
   multiple_results,
+  extra_action = "output_rows",
   query_struct = "ContributorSort",
   cli_route = "/projects/{id}/repository/contributors",
   cli_help = "Get repository contributors list.",
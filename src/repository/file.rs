@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Api, Clone, Default, Debug, Serialize, Deserialize)]
 #[api(endpoint(
   route = "/projects/{id}/repository/files/{file_path}",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/repository/files/{file_path}",
   query_struct = "FilePayload",
   cli_long_help = "Allows you to receive information about file in repository like name, size, content. File content is Base64 encoded.",
@@ -24,6 +25,7 @@ endpoint(
     route = "/projects/{id}/repository/files/{file_path}",
     method="POST",
     result_ok_status="CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/files/{file_path}/create",
     payload_struct = "FileCreatePayload",
     cli_help = "This allows you to create a single file.",
@@ -32,6 +34,7 @@ endpoint(
 endpoint(
     route = "/projects/{id}/repository/files/{file_path}",
     method="PUT",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/files/{file_path}/edit",
     payload_struct = "FileCreatePayload",
     cli_help = "This allows you to update a single file.",
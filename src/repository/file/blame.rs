@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/projects/{id}/repository/files/{file_path}/blame",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/projects/{id}/repository/files/{file_path}/blame",
   query_struct = "FilePayload",
   cli_long_help = "Allows you to receive blame information. Each blame range contains lines and corresponding commit information.",
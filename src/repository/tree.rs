@@ -1,13 +1,32 @@
+pub(crate) mod download;
+
+use clap::Command;
 use crud_api::{Api, ApiInput};
 use serde::{Deserialize, Serialize};
 
+/// Format of `/repository/archive`.
+const DEFAULT_ARCHIVE_FORMAT: &str = "tar.gz";
+
+// With `--download`, the list is run by `main`, see `commands`: the blobs of the tree
+// are fetched too.
 #[derive(Api, Default, Debug, Serialize, Deserialize)]
 #[api(endpoint(
   route = "/projects/{id}/repository/tree",
   query_struct = "TreeFilter",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/projects/{id}/repository/tree",
   cli_help = "Get a list of repository files and directories in a project.",
+  cli_long_help = "Get a list of repository files and directories in a project. With `--download`, the files of the whole subtree are downloaded in a local directory instead."
+))]
+// `archive` without format is `archive tar.gz`, see [declare].
+#[api(endpoint(
+  route = "/projects/{id}/repository/archive.{archive_format}",
+  stream,
+  query_struct = "ArchiveQuery",
+  cli_route = "/projects/{id}/repository/archive/{archive_format}",
+  cli_help = "Get an archive of the repository.",
+  cli_long_help = "Get an archive of the repository. The format is given by the optional argument: tar.gz (default), tar.bz2, tbz, tbz2, tb2, bz2, tar or zip."
 ))]
 pub(crate) struct Tree {
   mode: String,
@@ -19,6 +38,19 @@ pub(crate) struct Tree {
   path: String,
 }
 
+/// Makes the format of `repository archive` optional.
+pub(crate) fn declare(command: Command) -> Command {
+  command.mut_subcommand("projects", |projects| {
+    projects.mut_subcommand("repository", |repository| {
+      repository.mut_subcommand("archive", |archive| {
+        archive.mut_arg("archive_format", |arg| {
+          arg.default_value(DEFAULT_ARCHIVE_FORMAT)
+        })
+      })
+    })
+  })
+}
+
 #[derive(Debug, ApiInput, Serialize, Deserialize)]
 #[api(no_input_file)]
 pub(crate) struct TreeFilter {
@@ -51,6 +83,14 @@ pub(crate) struct TreeFilter {
   #[serde(skip_serializing_if = "Option::is_none")]
   recursive: Option<bool>,
 
+  #[api(
+    no_short,
+    help = "Download the files of the tree in the local directory DIR.",
+    long_help = "Download the files of the tree in the local directory DIR, with their path relative to `--path`. The tree is read recursively. The existing files are overwritten, but no file is written through an existing symbolic link."
+  )]
+  #[serde(skip)]
+  download: Option<String>,
+
   #[api(
     no_short,
     heading = "Pagination",
@@ -68,3 +108,23 @@ pub(crate) struct TreeFilter {
   )]
   per_page: Option<u32>,
 }
+
+#[derive(Debug, ApiInput, Serialize, Deserialize)]
+#[api(no_input_file)]
+pub(crate) struct ArchiveQuery {
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "The commit SHA, branch or tag to download (default: the default branch)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sha: Option<String>,
+
+  #[api(
+    no_short,
+    heading = "Filters",
+    help = "The subpath of the repository to download (default: the whole repository)."
+  )]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  path: Option<String>,
+}
@@ -0,0 +1,220 @@
+//! `repository tree --download DIR`: rebuilds a tree of the repository in a local
+//! directory, without git.
+//!
+//! The tree is listed recursively by pages, then its blobs are fetched in parallel and
+//! written with their path relative to `--path`. No file is written through an existing
+//! symbolic link: it could be left by a previous download, or written by the tree itself.
+
+use super::{Tree, TreeFilter};
+use crate::session::Session;
+use clap::ArgMatches;
+use crud_api::ApiInput;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use std::{
+  io::ErrorKind,
+  path::{Component, Path, PathBuf},
+};
+use tokio::task::JoinSet;
+
+/// Entries listed per request.
+const PER_PAGE: usize = 100;
+/// Blobs fetched at the same time.
+const PARALLEL_FETCHES: usize = 8;
+const EXECUTABLE_MODE: &str = "100755";
+const SYMLINK_MODE: &str = "120000";
+
+pub(crate) fn download_requested(matches: &ArgMatches) -> bool {
+  TreeFilter::from_clap_matches(matches)
+    .map(|filter| filter.download.is_some())
+    .unwrap_or(false)
+}
+
+/// Downloads the files of the tree given by `--path` and `--ref` in the directory given
+/// by `--download`.
+pub(crate) async fn run(matches: &ArgMatches) -> Result<i32> {
+  // The fetches are spawned: they borrow the session for the whole process.
+  let session = Session::current()?;
+  let id = session.arg("id").unwrap_or_default();
+  let mut filter = TreeFilter::from_clap_matches(matches)?;
+  let dir = PathBuf::from(filter.download.take().unwrap_or_default());
+  filter.recursive = Some(true);
+  let prefix = filter
+    .path
+    .as_deref()
+    .unwrap_or_default()
+    .trim_matches('/')
+    .to_string();
+
+  let entries = list(session, &id, &mut filter).await?;
+  tokio::fs::create_dir_all(&dir)
+    .await
+    .into_diagnostic()
+    .wrap_err_with(|| format!("Can't create the directory {}", dir.display()))?;
+  let mut blobs = vec![];
+  for entry in entries {
+    let local = local_path(&dir, &prefix, &entry.path)?;
+    match entry._type.as_str() {
+      "tree" => create_dir(&dir, &local).await?,
+      "blob" => blobs.push((entry, local)),
+      _ => eprintln!("Skipped the submodule {}", entry.path),
+    }
+  }
+
+  let count = blobs.len();
+  // The symbolic links are written last: no file is written through them.
+  let (links, files): (Vec<_>, Vec<_>) = blobs
+    .into_iter()
+    .partition(|(entry, _)| entry.mode == SYMLINK_MODE);
+  for blobs in [files, links] {
+    let mut fetches = JoinSet::new();
+    for (entry, local) in blobs {
+      if fetches.len() == PARALLEL_FETCHES {
+        joined(fetches.join_next().await)?;
+      }
+      let route = format!("/projects/{id}/repository/blobs/{}/raw", entry.id);
+      fetches.spawn(fetch(session, route, entry.mode, dir.clone(), local));
+    }
+    while let Some(fetched) = fetches.join_next().await {
+      joined(Some(fetched))?;
+    }
+  }
+  println!("Downloaded {count} files to {}", dir.display());
+  Ok(0)
+}
+
+/// Entries of all the pages of the tree.
+async fn list(session: &Session, id: &str, filter: &mut TreeFilter) -> Result<Vec<Tree>> {
+  let route = format!("/projects/{id}/repository/tree");
+  filter.per_page = Some(PER_PAGE as u32);
+  let mut entries = vec![];
+  for page in 1.. {
+    filter.page = Some(page);
+    let entries_page: Vec<Tree> = session
+      .get_with_query(&route, Some(&*filter))
+      .await
+      .wrap_err("Can't list the tree")?;
+    let last = entries_page.len() < PER_PAGE;
+    entries.extend(entries_page);
+    if last {
+      break;
+    }
+  }
+  Ok(entries)
+}
+
+/// Local path of the repository `path`, relative to the `prefix` of the tree.
+///
+/// The paths given by Gitlab are checked: they can't be written outside of `dir`.
+fn local_path(dir: &Path, prefix: &str, path: &str) -> Result<PathBuf> {
+  let relative = if prefix.is_empty() {
+    path
+  } else {
+    path
+      .strip_prefix(prefix)
+      .and_then(|path| path.strip_prefix('/'))
+      .ok_or_else(|| miette!("{path} is not in the tree {prefix}."))?
+  };
+  let relative = Path::new(relative);
+  if !relative
+    .components()
+    .all(|component| matches!(component, Component::Normal(_)))
+  {
+    return Err(miette!("{path} is not a path inside the repository."));
+  }
+  Ok(dir.join(relative))
+}
+
+/// Creates the directory `local` of `dir` and its missing parents.
+///
+/// Unlike `create_dir_all`, the existing symbolic links are not followed.
+async fn create_dir(dir: &Path, local: &Path) -> Result<()> {
+  let relative = local.strip_prefix(dir).into_diagnostic()?;
+  let mut path = dir.to_path_buf();
+  for component in relative.components() {
+    path.push(component);
+    match tokio::fs::symlink_metadata(&path).await {
+      Ok(metadata) if metadata.is_symlink() => {
+        return Err(miette!(
+          "{} is a symbolic link: no file is written through it.",
+          path.display()
+        ))
+      }
+      Ok(metadata) if metadata.is_dir() => {}
+      Ok(_) => return Err(miette!("{} is not a directory.", path.display())),
+      Err(err) if err.kind() == ErrorKind::NotFound => {
+        match tokio::fs::create_dir(&path).await {
+          // Created by another fetch in the meantime.
+          Err(err) if err.kind() == ErrorKind::AlreadyExists && path.is_dir() => {}
+          result => result
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Can't create the directory {}", path.display()))?,
+        }
+      }
+      Err(err) => {
+        return Err(err)
+          .into_diagnostic()
+          .wrap_err_with(|| format!("Can't read {}", path.display()))
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Writes the blob fetched from `route` to `local`, with its `mode`.
+async fn fetch(
+  session: &Session,
+  route: String,
+  mode: String,
+  dir: PathBuf,
+  local: PathBuf,
+) -> Result<()> {
+  let content = session
+    .get_raw(&route, &[])
+    .await
+    .wrap_err_with(|| format!("Can't fetch {}", local.display()))?
+    .body;
+  if let Some(parent) = local.parent() {
+    create_dir(&dir, parent).await?;
+  }
+  write(&local, &mode, content)
+    .await
+    .into_diagnostic()
+    .wrap_err_with(|| format!("Can't write {}", local.display()))
+}
+
+#[cfg(unix)]
+async fn write(local: &Path, mode: &str, content: Vec<u8>) -> std::io::Result<()> {
+  use std::{ffi::OsString, os::unix::prelude::*};
+
+  // A symbolic link written by a previous download is replaced, not followed.
+  if tokio::fs::symlink_metadata(local)
+    .await
+    .is_ok_and(|metadata| metadata.is_symlink())
+  {
+    tokio::fs::remove_file(local).await?;
+  }
+  if mode == SYMLINK_MODE {
+    // The content of a symbolic link is its target.
+    return tokio::fs::symlink(OsString::from_vec(content), local).await;
+  }
+  tokio::fs::write(local, content).await?;
+  let permissions = if mode == EXECUTABLE_MODE {
+    0o755
+  } else {
+    0o644
+  };
+  tokio::fs::set_permissions(local, std::fs::Permissions::from_mode(permissions)).await
+}
+
+/// The symbolic links are written as files holding their target.
+#[cfg(not(unix))]
+async fn write(local: &Path, _mode: &str, content: Vec<u8>) -> std::io::Result<()> {
+  tokio::fs::write(local, content).await
+}
+
+fn joined(fetched: Option<Result<Result<()>, tokio::task::JoinError>>) -> Result<()> {
+  match fetched {
+    Some(fetched) => fetched.into_diagnostic()?,
+    None => Ok(()),
+  }
+}
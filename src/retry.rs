@@ -0,0 +1,128 @@
+//! Retries of the requests rejected by Gitlab: rate limited (`429`) or unavailable (`502`, `503`).
+//!
+//! The requests of [Session](crate::session::Session) are retried with their `Retry-After`
+//! and `RateLimit-*` headers, or else with a jittered exponential backoff. Only the
+//! idempotent requests are retried, see [IDEMPOTENT_METHODS]: the others may have been
+//! processed. The requests of the generated commands are sent by the session too, through
+//! the [relay](crate::relay).
+//!
+//! The retries are logged as warnings: `RUST_LOG=warn gitlab-cli ...`.
+
+use chrono::DateTime;
+use clap::{value_parser, Arg, ArgMatches, Command};
+use hyper::{header::RETRY_AFTER, HeaderMap, Method, StatusCode};
+use std::{
+  collections::hash_map::RandomState,
+  hash::{BuildHasher, Hasher},
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Statuses of the requests retried by [Session](crate::session::Session).
+pub(crate) const RETRY_STATUSES: [StatusCode; 3] = [
+  StatusCode::TOO_MANY_REQUESTS,
+  StatusCode::BAD_GATEWAY,
+  StatusCode::SERVICE_UNAVAILABLE,
+];
+/// Methods of the requests that can be sent again.
+pub(crate) const IDEMPOTENT_METHODS: [Method; 3] = [Method::GET, Method::HEAD, Method::PUT];
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Number of retries and timeout of the requests.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Policy {
+  pub(crate) max_retries: u32,
+  pub(crate) timeout: Option<Duration>,
+}
+
+impl Policy {
+  /// Policy given by the arguments of the command.
+  pub(crate) fn new(matches: &ArgMatches) -> Self {
+    Policy {
+      max_retries: matches
+        .try_get_one::<u32>("max_retries")
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(DEFAULT_MAX_RETRIES),
+      timeout: matches
+        .try_get_one::<u64>("timeout")
+        .ok()
+        .flatten()
+        .map(|seconds| Duration::from_secs(*seconds)),
+    }
+  }
+}
+
+/// Declares the global `--max-retries` and `--timeout` arguments.
+pub(crate) fn declare(command: Command) -> Command {
+  command
+    .arg(
+      Arg::new("max_retries")
+        .long("max-retries")
+        .value_name("N")
+        .value_parser(value_parser!(u32))
+        .global(true)
+        .help("Retries of the requests rejected by Gitlab (429, 502, 503).")
+        .long_help(format!("Retries of the requests rejected by Gitlab: rate limited (429) or unavailable (502, 503). Only the GET, HEAD and PUT requests are retried. Default: {DEFAULT_MAX_RETRIES}.")),
+    )
+    .arg(
+      Arg::new("timeout")
+        .long("timeout")
+        .value_name("SECONDS")
+        .value_parser(value_parser!(u64))
+        .global(true)
+        .help("Timeout of each request."),
+    )
+}
+
+/// Delay before the retry of a rejected request.
+///
+/// `Retry-After` is given in seconds or as a date. `RateLimit-Reset` is the time when
+/// the rate limit is reset.
+pub(crate) fn delay(attempt: u32, headers: &HeaderMap) -> Duration {
+  let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+  let retry_after = header(RETRY_AFTER.as_str()).and_then(|value| {
+    value
+      .parse::<u64>()
+      .ok()
+      .map(Duration::from_secs)
+      .or_else(|| {
+        DateTime::parse_from_rfc2822(value)
+          .ok()
+          .and_then(|date| until(date.timestamp()))
+      })
+  });
+  retry_after
+    .or_else(|| rate_limit_reset(headers))
+    .unwrap_or_else(|| backoff(attempt))
+}
+
+/// Time to wait when the rate limit is exhausted: `RateLimit-Remaining` is `0`.
+pub(crate) fn rate_limit_wait(headers: &HeaderMap) -> Option<Duration> {
+  let remaining = headers.get("ratelimit-remaining")?.to_str().ok()?;
+  (remaining.trim() == "0")
+    .then(|| rate_limit_reset(headers))
+    .flatten()
+}
+
+fn rate_limit_reset(headers: &HeaderMap) -> Option<Duration> {
+  let reset = headers.get("ratelimit-reset")?.to_str().ok()?;
+  until(reset.trim().parse().ok()?)
+}
+
+/// Duration until the timestamp.
+fn until(timestamp: i64) -> Option<Duration> {
+  let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+  Some(Duration::from_secs((timestamp - now).max(1) as u64))
+}
+
+/// Exponential backoff with jitter: between the half and the whole of `1s * 2^attempt`.
+pub(crate) fn backoff(attempt: u32) -> Duration {
+  let backoff = INITIAL_BACKOFF
+    .saturating_mul(2u32.saturating_pow(attempt))
+    .min(MAX_BACKOFF);
+  let jitter = RandomState::new().build_hasher().finish() % 1000;
+  backoff / 2 + backoff / 2 * jitter as u32 / 1000
+}
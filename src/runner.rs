@@ -8,11 +8,13 @@ use serde::{Deserialize, Serialize};
     route = "/runners",
     query_struct = "RunnerFilter",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/runners",
     cli_help = "Get a list of specific runners available to the user."
   ),
   endpoint(
     route = "/runners/{id}",
+    extra_action = "output_result",
     cli_route = "/runners/{id}",
     cli_help = "Get details of a runner.",
     cli_long_help = "Get details of a runner. At least the Maintainer role is required to get runner details at the project and group level. Instance-level runner details via this endpoint are available to all signed in users."
@@ -21,6 +23,7 @@ use serde::{Deserialize, Serialize};
     route = "/runners/{id}",
     method = "PUT",
     payload_struct = "RunnerUpdate",
+    extra_action = "output_result",
     cli_route = "/runners/{id}/update",
     cli_help = "Update details of a runner.",
   ),
@@ -28,6 +31,7 @@ use serde::{Deserialize, Serialize};
     route = "/runners/all",
     query_struct = "RunnerFilter",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/runners/all",
     cli_help = "Get a list of all runners in the GitLab instance (specific and shared).",
     cli_long_help = "Get a list of all runners in the GitLab instance (specific and shared). Access is restricted to users with administrator access."
@@ -36,7 +40,8 @@ use serde::{Deserialize, Serialize};
     route = "/runners",
     payload_struct = "RunnerCreatePayload",
     method = "POST",
-    cli_route = "/runners/",
+    extra_action = "output_result",
+    cli_route = "/runners/register",
     cli_help = "Register a new runner for the instance."
   ),
   endpoint(
@@ -96,7 +101,8 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/runners",
     query_struct = "RunnerFilter",
     multiple_results,
-    cli_route = "/projects/{id}//runners",
+    extra_action = "output_rows",
+    cli_route = "/projects/{id}/runners",
     cli_help = "List all runners available in the project.",
     cli_long_help = "List all runners available in the project, including from ancestor groups and any allowed shared runners."
   ),
@@ -104,12 +110,14 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/runners",
     payload_struct = "RunnerId",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/runners/enable",
     cli_help = "Enable an available specific runner in the project."
   ),
   endpoint(
     route = "/projects/{id}/runners/{runner_id}",
     method = "DELETE",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/runners/{runner_id}/disable",
     cli_help = "Disable a specific runner from the project.",
     cli_long_help = "Disable a specific runner from the project. It works only if the project isn't
@@ -129,6 +137,7 @@ the only project associated with the specified runner."
     route = "/groups/{id}/runners",
     query_struct = "RunnerFilter",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/runners",
     cli_help = "List all runners available in the project.",
     cli_long_help = "List all runners available in the group as well as its ancestor groups, including any allowed shared runners."
@@ -150,7 +159,7 @@ pub struct Runner {
   paused: bool,
   is_shared: bool,
   runner_type: String,
-  name: String,
+  name: Option<String>,
   online: bool,
   status: String,
   #[api(table_skip)]
@@ -0,0 +1,427 @@
+use crate::{commands, pagination, relay, repository::tree, retry};
+use clap::{ArgMatches, Command};
+use config::Config;
+use crud_api::{DummyTryFrom, EmptyResponse};
+use crud_auth::CrudAuth;
+use http_body_util::BodyExt;
+use hyper::{
+  header::{HeaderMap, HeaderName, CONTENT_TYPE},
+  Method, Request, StatusCode, Uri,
+};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+  client::legacy::{connect::HttpConnector, Client},
+  rt::TokioExecutor,
+};
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+  fmt::Debug,
+  sync::{Mutex, OnceLock},
+  time::Instant,
+};
+
+/// Must be the same as the `base_url` declared in `main.rs`.
+const DEFAULT_BASE_URL: &str = "https://gitlab.com/api/v4";
+
+static SESSION: OnceLock<Session> = OnceLock::new();
+
+/// Bearer authentication that also records the session used by the current command.
+///
+/// The session allows resources to make their own calls to the API
+/// (for example to complete a result with the data of another endpoint).
+#[derive(Default, Debug)]
+pub(crate) struct Auth {
+  bearer: crud_auth_bearer::Auth,
+  header: (String, String),
+}
+
+impl CrudAuth for Auth {
+  fn clap_auth(&self, app: Command) -> Command {
+    commands::globals(self.bearer.clap_auth(app)).defer(init_commands)
+  }
+
+  fn clap_matches(&mut self, matches: &ArgMatches, app: &mut Command, settings: &Config) {
+    let session = Session::init(matches, settings);
+    self.header = session.auth.header.clone();
+    if pagination::requested(session.subcommands().1) {
+      pagination::record(app);
+    }
+  }
+
+  fn auth_header(&self) -> (String, String) {
+    self.header.clone()
+  }
+
+  fn error_help_message(&self) -> String {
+    self.bearer.error_help_message()
+  }
+}
+
+/// Deferred initialization of the commands, once all the subcommands are declared.
+fn init_commands(command: Command) -> Command {
+  relay::apply(commands::init(tree::declare(commands::declare(command))))
+}
+
+/// Base URL, credentials and arguments of the current command.
+#[derive(Debug)]
+pub(crate) struct Session {
+  base_url: String,
+  matches: ArgMatches,
+  auth: Auth,
+  retry: retry::Policy,
+  /// Client of all the requests: the connections are reused.
+  client: Client<HttpsConnector<HttpConnector>, String>,
+  /// Time until which the requests wait: the rate limit is exhausted.
+  rate_limited_until: Mutex<Option<Instant>>,
+}
+
+impl Session {
+  /// Session of the command matched by `matches`.
+  ///
+  /// The base URL and the token are given by the arguments, the profile or the settings.
+  pub(crate) fn new(matches: &ArgMatches, settings: &Config) -> Session {
+    let mut bearer = crud_auth_bearer::Auth::default();
+    // The command is not used by the bearer authentication.
+    bearer.clap_matches(matches, &mut Command::new(""), settings);
+    let base_url =
+      relay::upstream().map_or_else(|| Session::base_url(matches, settings), str::to_string);
+    Session {
+      base_url,
+      matches: matches.clone(),
+      auth: Auth {
+        header: bearer.auth_header(),
+        bearer,
+      },
+      retry: retry::Policy::new(matches),
+      client: Client::builder(TokioExecutor::new()).build(
+        HttpsConnectorBuilder::new()
+          .with_webpki_roots()
+          .https_or_http()
+          .enable_http1()
+          .build(),
+      ),
+      rate_limited_until: Mutex::new(None),
+    }
+  }
+
+  /// Base URL of the API given by the arguments, the profile or the settings.
+  pub(crate) fn base_url(matches: &ArgMatches, settings: &Config) -> String {
+    crud_api::settings::get_settings(settings, matches, "base_url")
+      .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+  }
+
+  /// Records the session of the command matched by `matches`, returned by [Session::current].
+  pub(crate) fn init(matches: &ArgMatches, settings: &Config) -> &'static Session {
+    SESSION.get_or_init(|| Session::new(matches, settings))
+  }
+
+  /// Returns the session of the current command.
+  pub(crate) fn current() -> Result<&'static Session> {
+    SESSION
+      .get()
+      .ok_or_else(|| miette!("The session is not initialized."))
+  }
+
+  /// Matches of the command.
+  pub(crate) fn matches(&self) -> &ArgMatches {
+    &self.matches
+  }
+
+  /// Host of the base URL, with its port: `gitlab.example.com`.
+  pub(crate) fn host(&self) -> &str {
+    let url = self
+      .base_url
+      .split_once("://")
+      .map_or(self.base_url.as_str(), |(_, url)| url);
+    url.split('/').next().unwrap_or_default()
+  }
+
+  /// Value of the argument `name` in the matched subcommands.
+  ///
+  /// The closest subcommand to the root wins: `id` is the id of the project in
+  /// `projects {id} releases ...`.
+  pub(crate) fn arg(&self, name: &str) -> Option<String> {
+    let mut matches = &self.matches;
+    loop {
+      if let Ok(Some(value)) = matches.try_get_one::<String>(name) {
+        return Some(value.clone());
+      }
+      matches = matches.subcommand()?.1;
+    }
+  }
+
+  /// Names of the matched subcommands and the arguments of the last one.
+  pub(crate) fn subcommands(&self) -> (Vec<&str>, &ArgMatches) {
+    let mut names = vec![];
+    let mut matches = &self.matches;
+    while let Some((name, sub_matches)) = matches.subcommand() {
+      names.push(name);
+      matches = sub_matches;
+    }
+    (names, matches)
+  }
+
+  /// `GET` the `route` (relative to the base URL) and deserialize the response.
+  pub(crate) async fn get<R>(&self, route: &str) -> Result<R>
+  where
+    R: Send + DeserializeOwned + Debug + Default,
+    DummyTryFrom: TryInto<R, Error = String>,
+  {
+    self.get_with_query(route, None::<EmptyResponse>).await
+  }
+
+  /// `GET` the `route` with the `query` arguments.
+  pub(crate) async fn get_with_query<R, Q>(&self, route: &str, query: Option<Q>) -> Result<R>
+  where
+    R: Send + DeserializeOwned + Debug + Default,
+    Q: Send + Serialize + Debug,
+    DummyTryFrom: TryInto<R, Error = String>,
+  {
+    let route = match query {
+      Some(query) => format!(
+        "{route}?{}",
+        serde_qs::to_string(&query)
+          .into_diagnostic()
+          .wrap_err("Can't serialize the query")?
+      ),
+      None => route.to_string(),
+    };
+    let response = self.get_raw(&route, &[]).await?;
+    if response.body.is_empty() {
+      return Ok(R::default());
+    }
+    serde_json::from_slice(&response.body)
+      .into_diagnostic()
+      .wrap_err("Can't deserialize the response")
+  }
+
+  /// `GET` the `route` and returns the raw response.
+  ///
+  /// Used when the response is not JSON or when its headers are needed.
+  /// The requests rejected by Gitlab are retried, see [retry].
+  pub(crate) async fn get_raw(
+    &self,
+    route: &str,
+    headers: &[(HeaderName, String)],
+  ) -> Result<RawResponse> {
+    self.send(Method::GET, route, headers, None).await
+  }
+
+  /// Sends a `method` request to the `route` with a JSON `payload`.
+  ///
+  /// Used by the commands that make several calls to the API, like `variables import`.
+  pub(crate) async fn send_json<P>(
+    &self,
+    method: Method,
+    route: &str,
+    payload: Option<&P>,
+  ) -> Result<RawResponse>
+  where
+    P: Serialize,
+  {
+    let body = payload
+      .map(serde_json::to_string)
+      .transpose()
+      .into_diagnostic()
+      .wrap_err("Can't serialize the payload")?;
+    self.send(method, route, &[], body).await
+  }
+
+  /// Whether the `route` is found: `HEAD` request answered by a `404` or a success.
+  ///
+  /// Used to check the files of a repository before committing them.
+  pub(crate) async fn exists(&self, route: &str) -> Result<bool> {
+    let uri = self.uri(route)?;
+    let response = self.send_retried(Method::HEAD, &uri, &[], None).await?;
+    if response.status == StatusCode::NOT_FOUND {
+      return Ok(false);
+    }
+    response_or_error(response, &uri).map(|_| true)
+  }
+
+  /// Sends the request, see [Session::send_retried], whatever the status of the response.
+  ///
+  /// Used by the [relay] of the requests of the generated commands.
+  pub(crate) async fn send_raw(
+    &self,
+    method: Method,
+    route: &str,
+    headers: &[(HeaderName, String)],
+    body: Option<String>,
+  ) -> Result<RawResponse> {
+    self
+      .send_retried(method, &self.uri(route)?, headers, body)
+      .await
+  }
+
+  /// Sends the request, see [Session::send_retried], and fails when it is not a success.
+  async fn send(
+    &self,
+    method: Method,
+    route: &str,
+    headers: &[(HeaderName, String)],
+    body: Option<String>,
+  ) -> Result<RawResponse> {
+    let uri = self.uri(route)?;
+    let response = self.send_retried(method, &uri, headers, body).await?;
+    response_or_error(response, &uri)
+  }
+
+  fn uri(&self, route: &str) -> Result<Uri> {
+    format!("{}{route}", self.base_url)
+      .parse()
+      .into_diagnostic()
+      .wrap_err("Error during URL parsing")
+  }
+
+  /// Sends the request and retries it when it is rejected by Gitlab or fails.
+  ///
+  /// Only the idempotent requests are retried, see [retry::IDEMPOTENT_METHODS]: the
+  /// other requests may have been processed.
+  async fn send_retried(
+    &self,
+    method: Method,
+    uri: &Uri,
+    headers: &[(HeaderName, String)],
+    body: Option<String>,
+  ) -> Result<RawResponse> {
+    let idempotent = retry::IDEMPOTENT_METHODS.contains(&method);
+    let mut attempt = 0;
+    loop {
+      self.wait_rate_limit().await;
+      let request = self.send_once(&method, uri, headers, body.clone());
+      let response = match self.retry.timeout {
+        Some(timeout) => tokio::time::timeout(timeout, request)
+          .await
+          .unwrap_or_else(|_| Err(miette!("The request timed out after {timeout:?}.")))
+          .wrap_err_with(|| format!("URL: {uri}")),
+        None => request.await,
+      };
+      let delay = match response {
+        Ok(ref response) if idempotent && retry::RETRY_STATUSES.contains(&response.status) => {
+          retry::delay(attempt, &response.headers)
+        }
+        Ok(response) => {
+          if let Some(wait) = retry::rate_limit_wait(&response.headers) {
+            *self
+              .rate_limited_until
+              .lock()
+              .unwrap_or_else(|err| err.into_inner()) = Some(Instant::now() + wait);
+          }
+          return Ok(response);
+        }
+        Err(_) if idempotent => retry::backoff(attempt),
+        Err(err) => return Err(err),
+      };
+      if attempt >= self.retry.max_retries {
+        return response;
+      }
+      attempt += 1;
+      match &response {
+        Ok(response) => log::warn!(
+          "{method} {uri}: {}, retry {attempt}/{} in {delay:.1?}",
+          response.status,
+          self.retry.max_retries
+        ),
+        Err(err) => log::warn!(
+          "{method} {uri}: {}, retry {attempt}/{} in {delay:.1?}",
+          err.root_cause(),
+          self.retry.max_retries
+        ),
+      }
+      tokio::time::sleep(delay).await;
+    }
+  }
+
+  /// Waits until the rate limit is reset, when a previous response exhausted it.
+  async fn wait_rate_limit(&self) {
+    let until = self
+      .rate_limited_until
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .take();
+    if let Some(until) = until {
+      let wait = until.saturating_duration_since(Instant::now());
+      if !wait.is_zero() {
+        log::warn!("Rate limit exhausted: waiting {wait:.1?}");
+        tokio::time::sleep(wait).await;
+      }
+    }
+  }
+
+  async fn send_once(
+    &self,
+    method: &Method,
+    uri: &Uri,
+    headers: &[(HeaderName, String)],
+    body: Option<String>,
+  ) -> Result<RawResponse> {
+    let mut request = Request::builder().method(method).uri(uri);
+    let (auth_key, auth_value) = self.auth.auth_header();
+    if !auth_key.is_empty() {
+      request = request.header(auth_key, auth_value);
+    }
+    for (key, value) in headers {
+      request = request.header(key, value);
+    }
+    if body.is_some() && !headers.iter().any(|(key, _)| key == CONTENT_TYPE) {
+      request = request.header(CONTENT_TYPE, "application/json");
+    }
+    let request = request
+      .body(body.unwrap_or_default())
+      .into_diagnostic()
+      .wrap_err("HTTP request preparation failed.")?;
+
+    let (parts, body) = self
+      .client
+      .request(request)
+      .await
+      .into_diagnostic()
+      .wrap_err_with(|| format!("URL: {uri}"))
+      .wrap_err("HTTP call fail")?
+      .into_parts();
+    let body = body
+      .collect()
+      .await
+      .into_diagnostic()
+      .wrap_err_with(|| format!("URL: {uri}"))
+      .wrap_err("Can't read the HTTP response")?
+      .to_bytes()
+      .to_vec();
+    Ok(RawResponse {
+      status: parts.status,
+      headers: parts.headers,
+      body,
+    })
+  }
+
+  /// Blocking version of [Session::get].
+  ///
+  /// Used from the `extra_action`s of the endpoints.
+  pub(crate) fn blocking_get<R>(&self, route: &str) -> Result<R>
+  where
+    R: Send + DeserializeOwned + Debug + Default,
+    DummyTryFrom: TryInto<R, Error = String>,
+  {
+    tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(self.get(route)))
+  }
+}
+
+/// Response of [Session::get_raw].
+#[derive(Debug)]
+pub(crate) struct RawResponse {
+  pub(crate) status: StatusCode,
+  pub(crate) headers: HeaderMap,
+  pub(crate) body: Vec<u8>,
+}
+
+fn response_or_error(response: RawResponse, uri: &Uri) -> Result<RawResponse> {
+  if response.status.is_success() {
+    Ok(response)
+  } else {
+    Err(miette!("{}", String::from_utf8_lossy(&response.body)))
+      .wrap_err_with(|| format!("URL: {uri}"))
+      .wrap_err(format!("Unexpected HTTP Status Code: {}", response.status))
+  }
+}
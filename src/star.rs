@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/projects/{id}/starrers",
   multiple_results,
+  extra_action = "output_rows",
   query_struct = "ProjectsUsersFilter",
   cli_route = "/projects/{id}/starrers",
   cli_help = "List the users who starred the specified project.",
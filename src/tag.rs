@@ -7,12 +7,14 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/projects/{id}/repository/tags",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "TagQuery",
     cli_route = "/projects/{id}/repository/tags",
     cli_help = "Get a list of repository tags from a project, sorted by update date and time in descending order.",
   ),
   endpoint(
     route = "/projects/{id}/repository/tags/{tag_name}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/tags/{tag_name}",
     cli_help = "Get a specific repository tag determined by its name.",
   ),
@@ -21,6 +23,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "CreateTagPayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/repository/tags/create",
     cli_help = "Creates a new tag in the repository that points to the supplied ref.",
   ),
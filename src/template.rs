@@ -0,0 +1,141 @@
+//! `--output-template`: renders each result with a [MiniJinja](https://docs.rs/minijinja) template.
+//!
+//! ```shell
+//! gitlab-cli projects 42 issues --output-template '{{ iid }} {{ title | truncate(40) }} {{ labels | join(", ") }}'
+//! ```
+//!
+//! The fields of the result are the variables of the template. Besides the builtin
+//! filters of MiniJinja (`join`, `upper`, `default`...), the templates have:
+//! - `date`: formats a date, `{{ created_at | date("%d/%m/%Y") }}`. The default format is `%Y-%m-%d %H:%M`.
+//! - `ago`: time elapsed since a date, `{{ updated_at | ago }}` gives `3 hours ago`.
+//! - `truncate`: truncates a text to a number of characters, `{{ title | truncate(30) }}`.
+//! - `color`: colors a text when the output is a terminal, `{{ status | color("green") }}`.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use is_terminal::IsTerminal;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use minijinja::{Environment, ErrorKind};
+use serde::Serialize;
+use std::io::{stdout, Write};
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+/// Names and ANSI codes of the colors of the `color` filter.
+const COLORS: [(&str, &str); 10] = [
+  ("black", "30"),
+  ("red", "31"),
+  ("green", "32"),
+  ("yellow", "33"),
+  ("blue", "34"),
+  ("magenta", "35"),
+  ("cyan", "36"),
+  ("white", "37"),
+  ("gray", "90"),
+  ("bold", "1"),
+];
+
+/// Source of the template given by `--output-template`: the template itself, or `@path` of a file.
+pub(crate) fn source(template: &str) -> Result<String> {
+  match template.strip_prefix('@') {
+    Some(path) => std::fs::read_to_string(path)
+      .into_diagnostic()
+      .wrap_err(format!("Can't read the template file `{path}`")),
+    None => Ok(template.to_string()),
+  }
+}
+
+/// Prints each result rendered by the template, followed by a new line.
+pub(crate) fn render<T: Serialize>(template: &str, results: &[T]) -> Result<()> {
+  let env = environment(stdout().is_terminal());
+  let template = env
+    .template_from_str(template)
+    .map_err(|err| miette!("Invalid template: {err:#}"))?;
+  let mut out = stdout().lock();
+  for result in results {
+    let rendered = template
+      .render(result)
+      .map_err(|err| miette!("Can't render the template: {err:#}"))?;
+    writeln!(out, "{rendered}").into_diagnostic()?;
+  }
+  out.flush().into_diagnostic()
+}
+
+/// Renders one result. The template is compiled for each result: used while the pages are fetched.
+pub(crate) fn render_to_string<T: Serialize>(template: &str, result: &T) -> Result<String> {
+  environment(stdout().is_terminal())
+    .render_str(template, result)
+    .map_err(|err| miette!("Can't render the template: {err:#}"))
+}
+
+fn environment(colors: bool) -> Environment<'static> {
+  let mut env = Environment::new();
+  env.add_filter("date", |date: String, format: Option<String>| {
+    parse_date(&date).map(|date| {
+      date
+        .with_timezone(&Local)
+        .format(format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT))
+        .to_string()
+    })
+  });
+  env.add_filter("ago", |date: String| parse_date(&date).map(ago));
+  env.add_filter("truncate", |text: String, length: usize| {
+    if text.chars().count() <= length {
+      text
+    } else {
+      let mut truncated: String = text.chars().take(length.saturating_sub(1)).collect();
+      truncated.push('…');
+      truncated
+    }
+  });
+  env.add_filter("color", move |text: String, color: String| {
+    let code = COLORS
+      .iter()
+      .find(|(name, _)| *name == color)
+      .map(|(_, code)| *code)
+      .ok_or_else(|| {
+        minijinja::Error::new(
+          ErrorKind::InvalidOperation,
+          format!("unknown color `{color}`"),
+        )
+      })?;
+    Ok(if colors {
+      format!("\x1b[{code}m{text}\x1b[0m")
+    } else {
+      text
+    })
+  });
+  env
+}
+
+/// Parses the dates of Gitlab: `2023-05-04T12:34:56.789Z` or `2023-05-04`.
+fn parse_date(date: &str) -> Result<DateTime<Utc>, minijinja::Error> {
+  DateTime::parse_from_rfc3339(date)
+    .map(|date| date.with_timezone(&Utc))
+    .or_else(|_| {
+      NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+    })
+    .map_err(|_| {
+      minijinja::Error::new(
+        ErrorKind::InvalidOperation,
+        format!("`{date}` is not a date"),
+      )
+    })
+}
+
+/// `3 hours ago`, `2 days ago`...
+fn ago(date: DateTime<Utc>) -> String {
+  let seconds = (Utc::now() - date).num_seconds();
+  if seconds < 0 {
+    return "in the future".to_string();
+  }
+  let (count, unit) = match seconds {
+    0..=59 => return "just now".to_string(),
+    60..=3599 => (seconds / 60, "minute"),
+    3600..=86399 => (seconds / 3600, "hour"),
+    86400..=2591999 => (seconds / 86400, "day"),
+    2592000..=31535999 => (seconds / 2592000, "month"),
+    _ => (seconds / 31536000, "year"),
+  };
+  let plural = if count > 1 { "s" } else { "" };
+  format!("{count} {unit}{plural} ago")
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[api(
   endpoint(
     route = "/projects/{id}/issues/{iid}/time_estimate",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/time_estimate",
     method = "POST",
     query_struct = "IssueTimeEstimateQuery",
@@ -13,6 +14,7 @@ use serde::{Deserialize, Serialize};
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/add_spent_time",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/add_spent_time",
     method = "POST",
     result_ok_status = "CREATED",
@@ -21,18 +23,21 @@ use serde::{Deserialize, Serialize};
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/reset_spent_time",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/reset_spent_time",
     method = "POST",
     cli_help = "Resets the total spent time for this issue to 0 seconds.",
   ),
   endpoint(
     route = "/projects/{id}/issues/{iid}/time_stats",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/issues/{iid}/time_stats",
     method = "GET",
     cli_help = "Get time tracking stats",
   ),
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/time_stats",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/time_stats",
     cli_help = "Get time tracking stats.",
   ),
@@ -40,6 +45,7 @@ use serde::{Deserialize, Serialize};
     route = "/projects/{id}/merge_requests/{iid}/time_estimate",
     method = "POST",
     query_struct = "IssueTimeEstimateQuery",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/time_stats/set",
     cli_help = "Sets an estimated time of work for this merge request.",
   ),
@@ -48,18 +54,21 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     result_ok_status = "CREATED",
     query_struct = "IssueAddSpentTimeQuery",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/time_stats/add",
     cli_help = "Adds spent time for this merge request.",
   ),
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/reset_time_estimate",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/time_stats/reset_estimate_time",
     cli_help = "Resets the estimated time for this merge request to 0 seconds.",
   ),
   endpoint(
     route = "/projects/{id}/merge_requests/{iid}/reset_spent_time",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/merge_requests/{iid}/time_stats/reset_spent_time",
     cli_help = "Resets the total spend time for this merge request to 0 seconds.",
   )
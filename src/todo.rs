@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/todos",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "TodoFilter",
     cli_route = "/todos",
     cli_help = "Returns a list of to-do items.",
@@ -16,6 +17,7 @@ use serde::{Deserialize, Serialize};
     route = "/todos/{id}/mark_as_done",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/todos/{id}/mark_as_done",
     cli_help = "Marks a single pending to-do item given by its ID for the current user as done.",
     cli_long_help = "Marks a single pending to-do item given by its ID for the current user as done. The to-do item marked as done is returned in the response.",
@@ -32,6 +34,7 @@ use serde::{Deserialize, Serialize};
 )]
 #[api(endpoint(
   route = "/projects/{id}/issues/{iid}/todo",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/issues/{iid}/todo",
   method = "POST",
   result_ok_status = "CREATED",
@@ -44,6 +47,7 @@ use serde::{Deserialize, Serialize};
 ))]
 #[api(endpoint(
   route = "/projects/{id}/merge_requests/{iid}/todo",
+  extra_action = "output_result",
   cli_route = "/projects/{id}/merge_requests/{iid}/todo",
   method = "POST",
   result_ok_status = "CREATED",
@@ -17,12 +17,14 @@ use strum_macros::Display;
   endpoint(
     route = "/users",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "UsersFilter",
     cli_route = "/users",
     cli_help = "Get a list of users.",
   ),
   endpoint(
     route = "/users/{id}",
+    extra_action = "output_result",
     cli_route = "/users/{id}",
     cli_help = "Get a single user.",
   ),
@@ -31,6 +33,7 @@ use strum_macros::Display;
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "UserCreatePayload",
+    extra_action = "output_result",
     cli_route = "/users/create",
     cli_help = "Creates a new user.",
   ),
@@ -38,6 +41,7 @@ use strum_macros::Display;
     route = "/users/{id}",
     method = "PUT",
     payload_struct = "UserUpdatePayload",
+    extra_action = "output_result",
     cli_route = "/users/{id}/edit",
     cli_help = "Modifies an existing user.",
   ),
@@ -64,6 +68,7 @@ use strum_macros::Display;
     route = "/users/{id}/follow",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/users/{id}/follow",
     cli_help = "Follow a user",
   ),
@@ -71,42 +76,49 @@ use strum_macros::Display;
     route = "/users/{id}/unfollow",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/users/{id}/unfollow",
     cli_help = "Unfollow a user",
   ),
   endpoint(
     route = "/users/{id}/followers",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/users/{id}/followers",
     cli_help = "Get the followers of a user.",
   ),
   endpoint(
     route = "/users/{id}/following",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/users/{id}/following",
     cli_help = "Get the list of users being followed.",
   ),
   endpoint(
     route = "/users/{id}/block",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/users/{id}/block",
     cli_help = "Blocks the specified user.",
   ),
   endpoint(
     route = "/users/{id}/unblock",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/users/{id}/unblock",
     cli_help = "Unblocks the specified user.",
   ),
   endpoint(
     route = "/users/{id}/desactivate",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/users/{id}/desactivate",
     cli_help = "Desactivates the specified user.",
   ),
   endpoint(
     route = "/users/{id}/activate",
     method = "POST",
+    extra_action = "output_result",
     cli_route = "/users/{id}/activate",
     cli_help = "Activates the specified user.",
   ),
@@ -114,6 +126,7 @@ use strum_macros::Display;
     route = "/users/{id}/approve",
     method = "POST",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/users/{id}/approve",
     cli_help = "Approves the specified user.",
   ),
@@ -150,12 +163,14 @@ use strum_macros::Display;
   endpoint(
     route = "/projects/{id}/issues/{iid}/participants",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/projects/{id}/issues/{iid}/participants",
     cli_help = "Participants on issues",
   ),
   endpoint(
     route = "/projects/{id}/users",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "ProjectsUsersFilter",
     cli_route = "/projects/{id}/users",
     cli_help = "Get the users list of a project.",
@@ -163,12 +178,14 @@ use strum_macros::Display;
   endpoint(
     route = "/projects/{id}/members",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "MembersFilter",
     cli_route = "/projects/{id}/members",
     cli_help = "Gets a list of project members viewable by the authenticated user.",
   ),
   endpoint(
     route = "/projects/{id}/members/{user_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/members/{user_id}",
     cli_help = "Gets a member of a project.",
     cli_long_help = "Gets a member of a project. Returns only direct members and not inherited members through ancestor groups.",
@@ -176,12 +193,14 @@ use strum_macros::Display;
   endpoint(
     route = "/projects/{id}/members/all",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "MembersFilter",
     cli_route = "/projects/{id}/members/all",
     cli_help = "Gets a list of project members viewable by the authenticated user, including inherited members and permissions through ancestor groups.",
   ),
   endpoint(
     route = "/projects/{id}/members/all/{user_id}",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/members/all/{user_id}",
     cli_help = "Gets a member of a project, including members inherited through ancestor groups.",
   ),
@@ -190,6 +209,7 @@ use strum_macros::Display;
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "MemberCreatePayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/members/add",
     cli_help = "Adds a member to a project.",
   ),
@@ -197,6 +217,7 @@ use strum_macros::Display;
     route = "/projects/{id}/members/{user_id}",
     method = "PUT",
     payload_struct = "MemberEditPayload",
+    extra_action = "output_result",
     cli_route = "/projects/{id}/members/{user_id}/edit",
     cli_help = "Updates a member to a project.",
   ),
@@ -212,6 +233,7 @@ use strum_macros::Display;
   endpoint(
     route = "/groups/{id}/members",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "MembersFilter",
     cli_route = "/groups/{id}/members",
     cli_help = "Gets a list of group members viewable by the authenticated user.",
@@ -219,6 +241,7 @@ use strum_macros::Display;
   ),
   endpoint(
     route = "/groups/{id}/members/{user_id}",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/members/{user_id}",
     cli_help = "Gets a member of a group.",
     cli_long_help = "Gets a member of a group. Returns only direct members and not inherited members through ancestor groups.",
@@ -226,6 +249,7 @@ use strum_macros::Display;
   endpoint(
     route = "/groups/{id}/members/all",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "MembersFilter",
     cli_route = "/groups/{id}/members/all",
     cli_help = "Gets a list of group or project members viewable by the authenticated user, including inherited members and permissions through ancestor groups.",
@@ -233,12 +257,14 @@ use strum_macros::Display;
   endpoint(
     route = "/groups/{id}/pending_members",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "MembersPagination",
     cli_route = "/groups/{id}/pending_members",
     cli_help = "For a group and its subgroups and projects, get a list of all members in an awaiting state and those who are invited but do not have a GitLab account.",
   ),
   endpoint(
     route = "/groups/{id}/members/all/{user_id}",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/members/all/{user_id}",
     cli_help = "Gets a member of a group, including members inherited through ancestor groups.",
   ),
@@ -247,6 +273,7 @@ use strum_macros::Display;
     method = "POST",
     result_ok_status = "CREATED",
     payload_struct = "MemberCreatePayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/members/add",
     cli_help = "Adds a member to a group.",
   ),
@@ -254,6 +281,7 @@ use strum_macros::Display;
     route = "/groups/{id}/members/{user_id}",
     method = "PUT",
     payload_struct = "MemberEditPayload",
+    extra_action = "output_result",
     cli_route = "/groups/{id}/members/{user_id}/edit",
     cli_help = "Updates a member to a group.",
   ),
@@ -269,6 +297,7 @@ use strum_macros::Display;
     route = "/groups/{id}/provisioned_users",
     query_struct = "GroupsProvisionedUsersQuery",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/groups/{id}/provisioned_users",
     cli_help = "Removes a user from a group.",
   )
@@ -276,11 +305,13 @@ use strum_macros::Display;
 #[api(endpoint(
   route = "/projects/{id}/merge_requests/{iid}/participants",
   multiple_results,
+  extra_action = "output_rows",
   cli_route = "/projects/{id}/merge_requests/{iid}/participants",
   cli_help = "Get a list of merge request participants.",
 ))]
 #[api(endpoint(
   route = "/user",
+  extra_action = "output_result",
   cli_route = "/user",
   query_struct = "CurrentUserSelector",
   cli_help = "List current user.",
@@ -298,7 +329,9 @@ pub(crate) struct User {
   #[api(table_skip)]
   web_url: String,
   #[api(table_skip)]
-  avatar_url: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[pretty(skip_none)]
+  avatar_url: Option<String>,
   #[pretty(skip_none)]
   #[serde(skip_serializing_if = "Option::is_none")]
   created_at: Option<String>,
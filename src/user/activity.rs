@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/user/activities",
   multiple_results,
+  extra_action = "output_rows",
   query_struct = "ActivityPagination",
   cli_route = "/user/activities",
   cli_help = "Get the last activity date for all users, sorted from oldest to newest.",
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Api, Serialize, Deserialize, Debug, Default, Clone)]
 #[api(endpoint(
   route = "/user_counts",
+  extra_action = "output_result",
   cli_route = "/user/counts",
   cli_help = "Get the counts (same as in top right menu) of the currently signed in user.",
 ))]
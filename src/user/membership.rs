@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[api(endpoint(
   route = "/users/{id}/memberships",
   multiple_results,
+  extra_action = "output_rows",
   query_struct = "MembershipPagination",
   cli_route = "/users/{id}/memberships",
   cli_help = "Lists all projects and groups a user is a member of.",
@@ -41,6 +42,7 @@ pub(crate) struct MembershipPagination {
 #[api(endpoint(
   route = "/groups/{id}/members/{user_id}/state",
   query_struct = "MembersStateQuery",
+  extra_action = "output_result",
   cli_route = "/groups/{id}/members/{user_id}/state",
   cli_help = "Changes the membership state of a user in a group.",
   cli_long_help = "Changes the membership state of a user in a group. The state is applied to all subgroups and projects.",
@@ -5,12 +5,14 @@ use serde::{Deserialize, Serialize};
 #[api(
   endpoint(
     route = "/user/preferences",
+    extra_action = "output_result",
     cli_route = "/user/preferences",
     cli_help = "Get a list of currently authenticated user's preferences.",
   ),
   endpoint(
     route = "/user/preferences",
     method = "PUT",
+    extra_action = "output_result",
     cli_route = "/user/preferences/edit",
     payload_struct = "UserPreferencesPayload",
     cli_help = "Update the current user's preferences.",
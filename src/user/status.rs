@@ -5,18 +5,21 @@ use serde::{Deserialize, Serialize};
 #[api(
   endpoint(
     route = "/user/status",
+    extra_action = "output_result",
     cli_route = "/user/status",
     cli_help = "Get the status of the currently signed in user",
   ),
   endpoint(
     route = "/user/status",
     method = "PUT",
+    extra_action = "output_result",
     cli_route = "/user/status/edit",
     payload_struct = "UserStatusPayload",
     cli_help = "Set the status of the current user.",
   ),
   endpoint(
     route = "/users/{id}/status",
+    extra_action = "output_result",
     cli_route = "/users/{id}/status",
     cli_help = "Get the status of a user.",
   )
@@ -6,12 +6,14 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/users/{id}/impersonation_tokens",
     multiple_results,
+    extra_action = "output_rows",
     query_struct = "TokenFilter",
     cli_route = "/users/{id}/impersonation_tokens",
     cli_help = "It retrieves every impersonation token of the user.",
   ),
   endpoint(
     route = "/users/{id}/impersonation_tokens/{tid}",
+    extra_action = "output_result",
     cli_route = "/users/{id}/impersonation_tokens/{tid}",
     cli_help = "It retrieves every impersonation token of the user.",
   ),
@@ -29,6 +31,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "TokenCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/users/{id}/impersonation_tokens/create",
     cli_help = "It creates a new impersonation token.",
     cli_long_help = "It creates a new impersonation token. Only administrators can do this. You are only able to create impersonation tokens to impersonate the user and perform both API calls and Git reads and writes. The user can't see these tokens in their profile settings page."
@@ -40,6 +43,7 @@ use serde::{Deserialize, Serialize};
     method = "POST",
     payload_struct = "TokenCreatePayload",
     result_ok_status = "CREATED",
+    extra_action = "output_result",
     cli_route = "/users/{id}/personal_access_tokens/create",
     cli_help = "Use this API to create a new personal access token.",
     cli_long_help = "Use this API to create a new personal access token. Token values are returned once so, make sure you save it as you can't access it again."
@@ -47,11 +51,13 @@ use serde::{Deserialize, Serialize};
   endpoint(
     route = "/personal_access_tokens",
     multiple_results,
+    extra_action = "output_rows",
     cli_route = "/personal_access_tokens",
     cli_help = "Get a list of personal access tokens.",
   ),
   endpoint(
     route = "/personal_access_tokens/{id}",
+    extra_action = "output_result",
     cli_route = "/personal_access_tokens/{id}",
     cli_help = "Get a single personal access token by its ID.",
   ),
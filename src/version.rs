@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Api, Serialize, Deserialize, Debug, Default, Clone)] // PrettyPrint
 #[api(endpoint(
   route = "/version",
+  extra_action = "output_result",
   cli_route = "/version",
   cli_help = "Retrieve version information for this GitLab instance.",
 ))]
@@ -0,0 +1,395 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+use serde_json::{json, Value};
+use std::{path::Path, process::Command};
+
+const STATUS: &str = r#"{
+  "id": 93,
+  "sha": "18f3e63d05582537db6d183d9d557be09e1f90c8",
+  "ref": "main",
+  "status": "success",
+  "name": "default",
+  "target_url": "https://ci.example.com/builds/1",
+  "description": "Build passed",
+  "coverage": 100.0,
+  "allow_failure": false,
+  "created_at": "2016-01-19T09:05:50.355Z",
+  "started_at": null,
+  "finished_at": "2016-01-19T09:05:50.365Z",
+  "author": {
+    "id": 1,
+    "name": "Administrator",
+    "username": "root",
+    "state": "active",
+    "avatar_url": "https://gitlab.example.com/uploads/user/avatar/1/avatar.png",
+    "web_url": "https://gitlab.example.com/root"
+  }
+}"#;
+
+#[test]
+fn list_commits() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/commits",
+    Response::ok(format!("[{}]", fixture("commit"))),
+  );
+  let commits = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "--ref-name",
+      "main",
+      "--path",
+      "src/main.rs",
+      "--since",
+      "2021-09-01T00:00:00Z",
+      "--with-stats",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(request.query_param("ref_name").as_deref(), Some("main"));
+  assert_eq!(request.query_param("path").as_deref(), Some("src/main.rs"));
+  assert_eq!(
+    request.query_param("since").as_deref(),
+    Some("2021-09-01T00:00:00Z")
+  );
+  assert_eq!(request.query_param("with_stats").as_deref(), Some("true"));
+  assert_eq!(commits[0]["stats"]["total"], 25);
+}
+
+#[test]
+fn cherry_pick_commit() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/5/repository/commits/6104942438c/cherry_pick",
+    Response::new(201, fixture("commit")),
+  );
+  stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "6104942438c",
+      "cherry_pick",
+      "--branch",
+      "stable",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().json(), json!({"branch": "stable"}));
+}
+
+#[test]
+fn list_commit_refs() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/commits/6104942438c/refs",
+    Response::ok(r#"[{"type": "tag", "name": "v1.1.0"}]"#),
+  );
+  let refs = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "6104942438c",
+      "refs",
+      "--type",
+      "tag",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().query_param("type").as_deref(), Some("tag"));
+  assert_eq!(refs, json!([{"type": "tag", "name": "v1.1.0"}]));
+}
+
+#[test]
+fn commit_diff() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/commits/6104942438c/diff",
+    Response::ok(
+      r#"[{
+        "diff": "@@ -71,6 +71,8 @@\n sudo -u git -H bundle exec rake migrate_keys RAILS_ENV=production\n",
+        "new_path": "doc/update/5.4-to-6.0.md",
+        "old_path": "doc/update/5.4-to-6.0.md",
+        "a_mode": null,
+        "b_mode": "100644",
+        "new_file": false,
+        "renamed_file": false,
+        "deleted_file": false
+      }]"#,
+    ),
+  );
+  let table = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "6104942438c",
+      "diff",
+    ])
+    .ok();
+
+  assert!(table.contains("doc/update/5.4-to-6.0.md"), "{table}");
+  assert!(!table.contains("rake migrate_keys"), "{table}");
+}
+
+#[test]
+fn post_commit_status() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/5/statuses/18f3e63d05582537db6d183d9d557be09e1f90c8",
+    Response::new(201, STATUS),
+  );
+  let status = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "18f3e63d05582537db6d183d9d557be09e1f90c8",
+      "statuses",
+      "create",
+      "--state",
+      "success",
+      "--target-url",
+      "https://ci.example.com/builds/1",
+      "--description",
+      "Build passed",
+      "--coverage",
+      "100",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(
+    stub.request().json(),
+    json!({
+      "state": "success",
+      "target_url": "https://ci.example.com/builds/1",
+      "description": "Build passed",
+      "coverage": 100.0
+    })
+  );
+  assert_eq!(status["status"], "success");
+  assert_eq!(status["author"]["username"], "root");
+}
+
+/// Runs `git args` in `dir`.
+fn git(dir: &Path, args: &[&str]) {
+  let status = Command::new("git")
+    .args(args)
+    .current_dir(dir)
+    .env("GIT_CEILING_DIRECTORIES", dir)
+    .env("GIT_CONFIG_NOSYSTEM", "1")
+    .env("HOME", dir)
+    .status()
+    .expect("Can't run git");
+  assert!(status.success(), "git {args:?} failed");
+}
+
+/// The action of the payload on `file_path`.
+fn action<'a>(payload: &'a Value, action: &str, file_path: &str) -> &'a Value {
+  payload["actions"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|a| a["action"] == action && a["file_path"] == file_path)
+    .unwrap_or_else(|| panic!("No {action} of {file_path} in {payload}"))
+}
+
+#[test]
+fn create_commit_from_paths() {
+  let stub = Stub::start();
+  stub
+    .on(
+      "HEAD",
+      "/projects/5/repository/files/README.md",
+      Response::ok(""),
+    )
+    .on(
+      "POST",
+      "/projects/5/repository/commits",
+      Response::new(201, fixture("commit")),
+    );
+  std::fs::write(stub.home().join("README.md"), "# Project\n").unwrap();
+  std::fs::create_dir(stub.home().join("assets")).unwrap();
+  std::fs::write(stub.home().join("assets/logo.png"), [0x89, b'P', 0xff]).unwrap();
+  let output = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "create",
+      "--branch",
+      "main",
+      "--commit-message",
+      "Update the docs",
+      "--path",
+      "README.md",
+      "--path",
+      "assets",
+      "--delete",
+      "old.md",
+    ])
+    .ok();
+
+  let requests = stub.requests();
+  let heads: Vec<&str> = requests
+    .iter()
+    .filter(|request| request.method == "HEAD")
+    .map(|request| request.path.as_str())
+    .collect();
+  assert_eq!(
+    heads,
+    [
+      "/projects/5/repository/files/README.md",
+      "/projects/5/repository/files/assets%2Flogo.png"
+    ]
+  );
+  assert_eq!(requests[0].query_param("ref").as_deref(), Some("main"));
+  let payload = requests.last().unwrap().json();
+  assert_eq!(payload["branch"], "main");
+  assert_eq!(payload["commit_message"], "Update the docs");
+  assert_eq!(
+    payload["actions"],
+    json!([
+      {"action": "update", "file_path": "README.md", "content": "# Project\n"},
+      {"action": "create", "file_path": "assets/logo.png", "content": "iVD/", "encoding": "base64"},
+      {"action": "delete", "file_path": "old.md"}
+    ])
+  );
+  assert!(output.contains("Committed 6104942438c to main"), "{output}");
+}
+
+#[test]
+fn create_commit_from_a_missing_path() {
+  let stub = Stub::start();
+  let run = stub.run(&[
+    "projects",
+    "5",
+    "repository",
+    "commits",
+    "create",
+    "--branch",
+    "main",
+    "--commit-message",
+    "Update the docs",
+    "--path",
+    "README.md",
+  ]);
+
+  assert!(!run.success());
+  assert!(run.stderr().contains("--delete"), "{}", run.stderr());
+  assert!(stub.requests().is_empty());
+}
+
+#[test]
+fn create_commit_from_staged_changes() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/5/repository/commits",
+    Response::new(201, fixture("commit")),
+  );
+  let dir = stub.home();
+  git(dir, &["init", "--quiet"]);
+  git(dir, &["config", "user.email", "bot@example.com"]);
+  git(dir, &["config", "user.name", "Bot"]);
+  for file in ["a.txt", "build.sh", "old.txt", "gone.txt"] {
+    std::fs::write(
+      dir.join(file),
+      format!("{file}\nwith enough lines\nto be renamed\n"),
+    )
+    .unwrap();
+  }
+  git(dir, &["add", "."]);
+  git(dir, &["commit", "--quiet", "-m", "Initial"]);
+  std::fs::write(dir.join("a.txt"), "changed\n").unwrap();
+  std::fs::write(dir.join("c.txt"), "new\n").unwrap();
+  git(dir, &["update-index", "--chmod=+x", "build.sh"]);
+  git(dir, &["mv", "old.txt", "new.txt"]);
+  git(dir, &["rm", "--quiet", "gone.txt"]);
+  git(dir, &["add", "a.txt", "c.txt"]);
+
+  let output = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "create",
+      "--branch",
+      "main",
+      "--commit-message",
+      "Staged",
+      "--staged",
+    ])
+    .ok();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["actions"].as_array().unwrap().len(), 5, "{payload}");
+  assert_eq!(action(&payload, "update", "a.txt")["content"], "changed\n");
+  assert_eq!(
+    action(&payload, "chmod", "build.sh")["execute_filemode"],
+    true
+  );
+  assert_eq!(action(&payload, "create", "c.txt")["content"], "new\n");
+  action(&payload, "delete", "gone.txt");
+  let moved = action(&payload, "move", "new.txt");
+  assert_eq!(moved["previous_path"], "old.txt");
+  assert!(moved.get("content").is_none(), "{payload}");
+  assert!(output.contains("move    old.txt -> new.txt"), "{output}");
+}
+
+#[test]
+fn create_commit_dry_run() {
+  let stub = Stub::start();
+  std::fs::write(stub.home().join("notes.txt"), "notes\n").unwrap();
+  let output = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "commits",
+      "create",
+      "--branch",
+      "docs",
+      "--start-branch",
+      "main",
+      "--commit-message",
+      "Notes",
+      "--path",
+      "notes.txt",
+      "--dry-run",
+    ])
+    .ok();
+
+  let request = stub.request();
+  assert_eq!(request.method, "HEAD");
+  assert_eq!(request.query_param("ref").as_deref(), Some("main"));
+  assert_eq!(
+    output,
+    "create  notes.txt\nDry run: nothing is committed.\n"
+  );
+}
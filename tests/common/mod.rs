@@ -0,0 +1,401 @@
+//! Stub of the Gitlab API, and runner of the `gitlab-cli` binary against it.
+//!
+//! The stub answers the routes registered with [Stub::on], or else its fallback
+//! response. It records every request it receives.
+
+#![allow(dead_code)]
+
+use std::{
+  io::{BufRead, BufReader, Read, Write},
+  net::{TcpListener, TcpStream},
+  path::{Path, PathBuf},
+  process::{Command, Output, Stdio},
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc, Arc, Mutex,
+  },
+  thread,
+  time::Duration,
+};
+
+/// Prefix of the routes of the API.
+pub const API: &str = "/api/v4";
+/// Token given to the commands.
+pub const TOKEN: &str = "glpat-stub";
+/// Time after which the command is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Request received by the stub.
+#[derive(Debug, Clone)]
+pub struct Request {
+  pub method: String,
+  /// Path of the request, without the API prefix: `/projects/42`.
+  pub path: String,
+  pub query: Option<String>,
+  pub headers: Vec<(String, String)>,
+  pub body: Vec<u8>,
+}
+
+impl Request {
+  /// Value of the header `name`.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Decoded pairs of the query string.
+  pub fn query_pairs(&self) -> Vec<(String, String)> {
+    self
+      .query
+      .as_deref()
+      .unwrap_or_default()
+      .split('&')
+      .filter(|pair| !pair.is_empty())
+      .map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (decode(key), decode(value))
+      })
+      .collect()
+  }
+
+  /// Decoded value of the query parameter `name`.
+  pub fn query_param(&self, name: &str) -> Option<String> {
+    self
+      .query_pairs()
+      .into_iter()
+      .find(|(key, _)| key == name)
+      .map(|(_, value)| value)
+  }
+
+  /// The body parsed as JSON.
+  pub fn json(&self) -> serde_json::Value {
+    serde_json::from_slice(&self.body).unwrap_or_else(|err| {
+      panic!(
+        "The body of {} {} is not JSON: {err}\n{}",
+        self.method,
+        self.path,
+        String::from_utf8_lossy(&self.body)
+      )
+    })
+  }
+}
+
+/// Response of the stub.
+#[derive(Debug, Clone)]
+pub struct Response {
+  pub status: u16,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl Response {
+  pub fn new(status: u16, body: impl Into<String>) -> Self {
+    Response {
+      status,
+      headers: vec![],
+      body: body.into(),
+    }
+  }
+
+  pub fn ok(body: impl Into<String>) -> Self {
+    Response::new(200, body)
+  }
+
+  /// The fixture `tests/fixtures/{name}.json` with a `200` status.
+  pub fn fixture(name: &str) -> Self {
+    Response::ok(fixture(name))
+  }
+
+  pub fn header(mut self, name: &str, value: &str) -> Self {
+    self.headers.push((name.to_string(), value.to_string()));
+    self
+  }
+}
+
+struct Route {
+  method: String,
+  path: String,
+  responses: Vec<Response>,
+}
+
+#[derive(Default)]
+struct State {
+  routes: Vec<Route>,
+  fallback: Option<Response>,
+  requests: Vec<Request>,
+}
+
+/// Stub of the Gitlab API listening on a local port.
+pub struct Stub {
+  port: u16,
+  state: Arc<Mutex<State>>,
+  home: PathBuf,
+}
+
+impl Stub {
+  pub fn start() -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Can't bind the stub");
+    let port = listener.local_addr().unwrap().port();
+    let state = Arc::new(Mutex::new(State::default()));
+    let server_state = state.clone();
+    thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        let state = server_state.clone();
+        thread::spawn(move || serve(stream, &state));
+      }
+    });
+    Stub {
+      port,
+      state,
+      home: temp_dir(),
+    }
+  }
+
+  /// Answers the requests `method path` with `response`. When several responses are
+  /// registered for the same route, they are used in turn, the last one being repeated.
+  pub fn on(&self, method: &str, path: &str, response: Response) -> &Self {
+    let mut state = self.state.lock().unwrap();
+    match state
+      .routes
+      .iter_mut()
+      .find(|route| route.method == method && route.path == path)
+    {
+      Some(route) => route.responses.push(response),
+      None => state.routes.push(Route {
+        method: method.to_string(),
+        path: path.to_string(),
+        responses: vec![response],
+      }),
+    }
+    self
+  }
+
+  /// Answers the requests of the routes not registered with `response`. Without a
+  /// fallback, they are answered with a `404`.
+  pub fn fallback(&self, response: Response) -> &Self {
+    self.state.lock().unwrap().fallback = Some(response);
+    self
+  }
+
+  /// Forgets the routes and the requests.
+  pub fn reset(&self) {
+    *self.state.lock().unwrap() = State::default();
+  }
+
+  pub fn requests(&self) -> Vec<Request> {
+    self.state.lock().unwrap().requests.clone()
+  }
+
+  /// The only request received by the stub.
+  pub fn request(&self) -> Request {
+    let requests = self.requests();
+    assert_eq!(
+      requests.len(),
+      1,
+      "Expected one request, received: {requests:#?}"
+    );
+    requests[0].clone()
+  }
+
+  pub fn base_url(&self) -> String {
+    format!("http://127.0.0.1:{}{API}", self.port)
+  }
+
+  /// Web URL of `path` on the stub: `http://127.0.0.1:port/group/project`.
+  pub fn web_url(&self, path: &str) -> String {
+    format!("http://127.0.0.1:{}/{path}", self.port)
+  }
+
+  /// Home directory of the commands: it has no configuration file.
+  pub fn home(&self) -> &Path {
+    &self.home
+  }
+
+  /// Runs `gitlab-cli args` against the stub, outside of any git working tree.
+  pub fn run(&self, args: &[&str]) -> Run {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_gitlab-cli"));
+    command
+      .args(["--base-url", &self.base_url(), "--auth-token", TOKEN])
+      .args(args)
+      .current_dir(&self.home)
+      .env("HOME", &self.home)
+      .env("XDG_CONFIG_HOME", self.home.join(".config"))
+      .env("GIT_CEILING_DIRECTORIES", &self.home)
+      .env_remove("GITLAB_BASE_URL")
+      .env_remove("GITLAB_AUTH_TOKEN")
+      .env_remove("RUST_LOG")
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped());
+    let child = command.spawn().expect("Can't run gitlab-cli");
+    // The output is read while the command runs: it can exceed the buffer of the pipes.
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(child.wait_with_output()));
+    match receiver.recv_timeout(COMMAND_TIMEOUT) {
+      Ok(output) => Run(output.expect("Can't read the output of gitlab-cli")),
+      Err(_) => panic!("gitlab-cli {args:?} timed out"),
+    }
+  }
+}
+
+/// Output of a command.
+pub struct Run(pub Output);
+
+impl Run {
+  pub fn success(&self) -> bool {
+    self.0.status.success()
+  }
+
+  pub fn stdout(&self) -> String {
+    String::from_utf8_lossy(&self.0.stdout).to_string()
+  }
+
+  pub fn stderr(&self) -> String {
+    String::from_utf8_lossy(&self.0.stderr).to_string()
+  }
+
+  /// Asserts that the command succeeded, and returns its output.
+  pub fn ok(&self) -> String {
+    assert!(
+      self.success(),
+      "The command failed: {}\n{}",
+      self.0.status,
+      self.stderr()
+    );
+    self.stdout()
+  }
+
+  /// Asserts that the command succeeded, and parses its output as JSON.
+  pub fn json(&self) -> serde_json::Value {
+    let stdout = self.ok();
+    serde_json::from_str(&stdout)
+      .unwrap_or_else(|err| panic!("The output is not JSON: {err}\n{stdout}"))
+  }
+}
+
+/// Content of the fixture `tests/fixtures/{name}.json`: responses recorded from Gitlab.
+pub fn fixture(name: &str) -> String {
+  let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("tests/fixtures")
+    .join(format!("{name}.json"));
+  std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+}
+
+/// A new empty directory.
+pub fn temp_dir() -> PathBuf {
+  static COUNTER: AtomicUsize = AtomicUsize::new(0);
+  let dir = std::env::temp_dir().join(format!(
+    "gitlab-cli-test-{}-{}",
+    std::process::id(),
+    COUNTER.fetch_add(1, Ordering::Relaxed)
+  ));
+  std::fs::remove_dir_all(&dir).ok();
+  std::fs::create_dir_all(&dir).unwrap();
+  dir
+}
+
+fn serve(stream: TcpStream, state: &Mutex<State>) {
+  let mut reader = BufReader::new(stream.try_clone().unwrap());
+  let mut stream = stream;
+  let mut line = String::new();
+  if reader.read_line(&mut line).unwrap_or(0) == 0 {
+    return;
+  }
+  let mut parts = line.split_whitespace();
+  let method = parts.next().unwrap_or_default().to_string();
+  let target = parts.next().unwrap_or_default();
+  // The target is in absolute form when the request is sent by the session.
+  let target = match target.split_once("://") {
+    Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+    None => target,
+  };
+  let (path, query) = match target.split_once('?') {
+    Some((path, query)) => (path, Some(query.to_string())),
+    None => (target, None),
+  };
+  let path = path.strip_prefix(API).unwrap_or(path).to_string();
+
+  let mut headers = vec![];
+  loop {
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+      break;
+    }
+    let line = line.trim_end();
+    if line.is_empty() {
+      break;
+    }
+    if let Some((key, value)) = line.split_once(':') {
+      headers.push((key.trim().to_string(), value.trim().to_string()));
+    }
+  }
+  let length = headers
+    .iter()
+    .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+    .and_then(|(_, value)| value.parse().ok())
+    .unwrap_or(0);
+  let mut body = vec![0; length];
+  reader.read_exact(&mut body).ok();
+
+  let response = {
+    let mut state = state.lock().unwrap();
+    let response = match state
+      .routes
+      .iter_mut()
+      .find(|route| route.method == method && route.path == path)
+    {
+      Some(route) if route.responses.len() > 1 => Some(route.responses.remove(0)),
+      Some(route) => route.responses.first().cloned(),
+      None => state.fallback.clone(),
+    };
+    state.requests.push(Request {
+      method,
+      path,
+      query,
+      headers,
+      body,
+    });
+    response.unwrap_or_else(|| Response::new(404, r#"{"message":"404 Not Found"}"#))
+  };
+
+  let mut head = format!(
+    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+    response.status,
+    response.body.len()
+  );
+  for (name, value) in &response.headers {
+    head.push_str(&format!("{name}: {value}\r\n"));
+  }
+  head.push_str("\r\n");
+  stream.write_all(head.as_bytes()).ok();
+  stream.write_all(response.body.as_bytes()).ok();
+  stream.flush().ok();
+}
+
+/// Decodes the percent-encoding and the `+` of a query string.
+pub fn decode(value: &str) -> String {
+  let bytes = value.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    match bytes[i] {
+      b'+' => decoded.push(b' '),
+      b'%' => match value
+        .get(i + 1..i + 3)
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+      {
+        Some(byte) => {
+          decoded.push(byte);
+          i += 2;
+        }
+        None => decoded.push(b'%'),
+      },
+      byte => decoded.push(byte),
+    }
+    i += 1;
+  }
+  String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{Response, Stub};
+use serde_json::json;
+
+const DEPLOY_KEY: &str = r#"{
+  "id": 1,
+  "title": "Public key",
+  "key": "ssh-rsa AAAAB3NzaC1yc2EAAAABJQAAAIEAiPWx6WM4lhHNedGfBpPJNPpZ7yKu+dnn1SJejgt4596k6YjzGGphH2TUxwKzxcKDKKezwkpfnxPkSMkuEspGRt/aZZ9wa++Oi7Qkr8prgHc4soW6NUlfDzpvZK2H5E7eQaSeP3SAwGmQKUFHCddNaP0L+hM7zhFNzjFvpaMgJw0=",
+  "fingerprint": "4a:9d:64:15:ed:3a:e6:07:6e:89:36:b3:3b:03:05:d9",
+  "fingerprint_sha256": "SHA256:Jrs3LD1Ji30xNLtTVf9NDCj7kkBgPBb2pjvTZ3HfIgU",
+  "created_at": "2013-10-02T10:12:29Z",
+  "expires_at": null,
+  "can_push": false
+}"#;
+
+const DEPLOY_TOKEN: &str = r#"{
+  "id": 1,
+  "name": "My deploy token",
+  "username": "gitlab+deploy-token-1",
+  "expires_at": "2020-02-14T00:00:00.000Z",
+  "scopes": ["read_repository", "read_registry"],
+  "revoked": false,
+  "expired": false
+}"#;
+
+#[test]
+fn add_deploy_key() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/5/deploy_keys",
+    Response::new(201, DEPLOY_KEY),
+  );
+  let key = stub
+    .run(&[
+      "projects",
+      "5",
+      "deploy_keys",
+      "add",
+      "--title",
+      "Public key",
+      "--key",
+      "ssh-rsa AAAA",
+      "--can-push",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(
+    stub.request().json(),
+    json!({"title": "Public key", "key": "ssh-rsa AAAA", "can_push": true})
+  );
+  assert_eq!(key["id"], 1);
+}
+
+#[test]
+fn enable_deploy_key() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/5/deploy_keys/13/enable",
+    Response::new(201, DEPLOY_KEY),
+  );
+  stub
+    .run(&["projects", "5", "deploy_keys", "13", "enable", "-f", "json"])
+    .json();
+
+  assert_eq!(stub.request().method, "POST");
+}
+
+#[test]
+fn forbid_deploy_key_to_push() {
+  let stub = Stub::start();
+  stub.on(
+    "PUT",
+    "/projects/5/deploy_keys/11",
+    Response::ok(DEPLOY_KEY),
+  );
+  stub
+    .run(&[
+      "projects",
+      "5",
+      "deploy_keys",
+      "11",
+      "update",
+      "--can-push",
+      "false",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().json(), json!({"can_push": "false"}));
+}
+
+#[test]
+fn list_active_deploy_tokens() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/groups/1/deploy_tokens",
+    Response::ok(format!("[{DEPLOY_TOKEN}]")),
+  );
+  let tokens = stub
+    .run(&["groups", "1", "deploy_tokens", "--active", "-f", "json"])
+    .json();
+
+  assert_eq!(
+    stub.request().query_param("active").as_deref(),
+    Some("true")
+  );
+  assert_eq!(tokens[0]["username"], "gitlab+deploy-token-1");
+  assert!(tokens[0].get("token").is_none());
+}
+
+#[test]
+fn create_deploy_token_shows_its_secret() {
+  let stub = Stub::start();
+  let mut created: serde_json::Value = serde_json::from_str(DEPLOY_TOKEN).unwrap();
+  created["token"] = json!("jMRvtPNxrn3crTAGukpZ");
+  stub.on(
+    "POST",
+    "/projects/5/deploy_tokens",
+    Response::new(201, created.to_string()),
+  );
+  let token = stub
+    .run(&[
+      "projects",
+      "5",
+      "deploy_tokens",
+      "create",
+      "--name",
+      "My deploy token",
+      "--scopes",
+      "read_repository",
+      "--scopes",
+      "read_registry",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(
+    stub.request().json(),
+    json!({"name": "My deploy token", "scopes": ["read_repository", "read_registry"]})
+  );
+  assert_eq!(token["token"], "jMRvtPNxrn3crTAGukpZ");
+}
+
+#[test]
+fn revoke_deploy_token() {
+  let stub = Stub::start();
+  stub.on(
+    "DELETE",
+    "/groups/1/deploy_tokens/7",
+    Response::new(204, ""),
+  );
+  stub
+    .run(&["groups", "1", "deploy_tokens", "7", "revoke"])
+    .ok();
+
+  assert_eq!(stub.request().method, "DELETE");
+}
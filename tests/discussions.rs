@@ -0,0 +1,112 @@
+mod common;
+
+use common::{Response, Stub};
+
+const DISCUSSIONS: &str = r#"[
+  {
+    "id": "6a9c1750b37d513a43987b574953fceb50b03ce7",
+    "individual_note": false,
+    "notes": [
+      {
+        "id": 1126,
+        "type": "DiscussionNote",
+        "body": "Should we rename it?",
+        "author": {"id": 1, "username": "jdoe", "name": "John Doe", "state": "active", "web_url": ""},
+        "created_at": "2018-03-03T21:54:39.668Z",
+        "updated_at": "2018-03-03T21:54:39.668Z",
+        "system": false,
+        "noteable_type": "MergeRequest",
+        "resolvable": true,
+        "resolved": false
+      },
+      {
+        "id": 1129,
+        "type": "DiscussionNote",
+        "body": "Yes, it is done.",
+        "author": {"id": 2, "username": "asmith", "name": "Anna Smith", "state": "active", "web_url": ""},
+        "created_at": "2018-03-04T13:38:02.127Z",
+        "updated_at": "2018-03-04T13:38:02.127Z",
+        "system": false,
+        "noteable_type": "MergeRequest",
+        "resolvable": true,
+        "resolved": false
+      }
+    ]
+  }
+]"#;
+
+#[test]
+fn list_merge_request_discussions() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/merge_requests/11/discussions",
+    Response::ok(DISCUSSIONS),
+  );
+  let discussions = stub
+    .run(&[
+      "projects",
+      "5",
+      "merge_requests",
+      "11",
+      "discussions",
+      "--page",
+      "2",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().query_param("page").as_deref(), Some("2"));
+  assert_eq!(
+    discussions[0]["id"],
+    "6a9c1750b37d513a43987b574953fceb50b03ce7"
+  );
+  assert_eq!(discussions[0]["notes"][1]["author"]["username"], "asmith");
+
+  let table = stub
+    .run(&["projects", "5", "merge_requests", "11", "discussions"])
+    .ok();
+  assert!(
+    table.contains("6a9c1750b37d513a43987b574953fceb50b03ce7"),
+    "{table}"
+  );
+}
+
+#[test]
+fn merge_request_conversation() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/merge_requests/11/discussions",
+    Response::ok(DISCUSSIONS),
+  );
+  let conversation = stub
+    .run(&["projects", "5", "merge_requests", "11", "conversation"])
+    .ok();
+
+  assert_eq!(
+    stub.request().path,
+    "/projects/5/merge_requests/11/discussions"
+  );
+  assert!(
+    conversation.contains("Should we rename it?"),
+    "{conversation}"
+  );
+  assert!(conversation.contains("Yes, it is done."), "{conversation}");
+}
+
+#[test]
+fn issue_conversation() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/issues/3/discussions",
+    Response::ok(DISCUSSIONS),
+  );
+  let conversation = stub
+    .run(&["projects", "5", "issues", "3", "conversation", "-f", "json"])
+    .json();
+
+  assert_eq!(conversation[0]["notes"][0]["body"], "Should we rename it?");
+}
@@ -0,0 +1,608 @@
+//! Runs every endpoint declared with `#[api(endpoint(...))]` against the stub, and checks
+//! the method, the URL and the payload of its request.
+
+mod common;
+
+use common::{Response, Stub, TOKEN};
+use serde_json::Value;
+use std::{collections::HashSet, path::Path};
+
+/// What the command sends besides its ids.
+enum Sent {
+  Nothing,
+  /// The payload, when it is given by the arguments.
+  Payload(&'static str),
+  /// Pairs of the query string.
+  Query(&'static [(&'static str, &'static str)]),
+}
+
+/// Arguments required by the commands, and what they send. The arguments of a command
+/// are required by its subcommands too.
+const REQUIRED_ARGS: [(&str, &str, Sent); 40] = [
+  (
+    "/admin/ci/variables/create",
+    "--key KEY --value value",
+    Sent::Payload(r#"{"key":"KEY","value":"value"}"#),
+  ),
+  (
+    "/admin/ci/variables/{var_key}/update",
+    "--value value",
+    Sent::Payload(r#"{"value":"value"}"#),
+  ),
+  (
+    "/job",
+    "--job-token token",
+    Sent::Query(&[("job_token", "token")]),
+  ),
+  (
+    "/keys/by_fingerprint",
+    "--fingerprint SHA256:key",
+    Sent::Query(&[("fingerprint", "SHA256:key")]),
+  ),
+  (
+    "/groups/{id}/members/{user_id}/state",
+    "--state active",
+    Sent::Query(&[("state", "active")]),
+  ),
+  (
+    "/groups/{id}/deploy_tokens/create",
+    "--name ci --scopes read_repository",
+    Sent::Payload(r#"{"name":"ci","scopes":["read_repository"]}"#),
+  ),
+  (
+    "/groups/{id}/variables/create",
+    "--key KEY --value value",
+    Sent::Payload(r#"{"key":"KEY","value":"value"}"#),
+  ),
+  (
+    "/groups/{id}/variables/{var_key}/update",
+    "--value value",
+    Sent::Payload(r#"{"value":"value"}"#),
+  ),
+  (
+    "/projects/{id}/artifacts/{ref_name}/download",
+    "--job build",
+    Sent::Query(&[("job", "build")]),
+  ),
+  (
+    "/projects/{id}/artifacts/{ref_name}/raw/{artifact_path}",
+    "--job build",
+    Sent::Query(&[("job", "build")]),
+  ),
+  (
+    "/projects/{id}/deploy_keys/add",
+    "--title ci --key ssh-ed25519",
+    Sent::Payload(r#"{"title":"ci","key":"ssh-ed25519"}"#),
+  ),
+  (
+    "/projects/{id}/deploy_tokens/create",
+    "--name ci --scopes read_repository",
+    Sent::Payload(r#"{"name":"ci","scopes":["read_repository"]}"#),
+  ),
+  (
+    "/projects/{id}/issues/{iid}/move",
+    "--to-project-id 7",
+    Sent::Payload(r#"{"to_project_id":7}"#),
+  ),
+  (
+    "/projects/{id}/issues/{iid}/time_estimate",
+    "--duration 3h30m",
+    Sent::Query(&[("duration", "3h30m")]),
+  ),
+  (
+    "/projects/{id}/issues/{iid}/add_spent_time",
+    "--duration 1h",
+    Sent::Query(&[("duration", "1h")]),
+  ),
+  (
+    "/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/resolve",
+    "--resolved true",
+    Sent::Query(&[("resolved", "true")]),
+  ),
+  (
+    "/projects/{id}/merge_requests/{iid}/time_stats/set",
+    "--duration 3h30m",
+    Sent::Query(&[("duration", "3h30m")]),
+  ),
+  (
+    "/projects/{id}/merge_requests/{iid}/time_stats/add",
+    "--duration 1h",
+    Sent::Query(&[("duration", "1h")]),
+  ),
+  (
+    "/projects/{id}/pipeline_schedules/create",
+    "--description Nightly --ref main --cron @daily",
+    Sent::Payload(r#"{"description":"Nightly","ref":"main","cron":"@daily"}"#),
+  ),
+  (
+    "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/create",
+    "--key KEY --value value",
+    Sent::Payload(r#"{"key":"KEY","value":"value"}"#),
+  ),
+  (
+    "/projects/{id}/pipeline_schedules/{pipeline_schedule_id}/variables/{var_key}/edit",
+    "--value value",
+    Sent::Payload(r#"{"value":"value"}"#),
+  ),
+  (
+    "/projects/{id}/pipelines/create",
+    "--ref main",
+    Sent::Payload(r#"{"ref":"main"}"#),
+  ),
+  (
+    "/projects/{id}/protected_branches/protect",
+    "--name main",
+    Sent::Payload(r#"{"name":"main"}"#),
+  ),
+  (
+    "/projects/{id}/protected_tags/protect",
+    "--name v*",
+    Sent::Payload(r#"{"name":"v*"}"#),
+  ),
+  (
+    "/projects/{id}/repository/changelog",
+    "--version 1.0.0",
+    Sent::Query(&[("version", "1.0.0")]),
+  ),
+  (
+    "/projects/{id}/repository/changelog/generate",
+    "--version 1.0.0",
+    Sent::Query(&[("version", "1.0.0")]),
+  ),
+  (
+    "/projects/{id}/repository/commits/{sha}/cherry_pick",
+    "--branch main",
+    Sent::Payload(r#"{"branch":"main"}"#),
+  ),
+  (
+    "/projects/{id}/repository/commits/{sha}/revert",
+    "--branch main",
+    Sent::Payload(r#"{"branch":"main"}"#),
+  ),
+  (
+    "/projects/{id}/repository/commits/{sha}/statuses/create",
+    "--state success",
+    Sent::Payload(r#"{"state":"success"}"#),
+  ),
+  (
+    "/projects/{id}/repository/compare",
+    "--from main --to dev",
+    Sent::Query(&[("from", "main"), ("to", "dev")]),
+  ),
+  (
+    "/projects/{id}/repository/files/{file_path}/create",
+    "--branch main --content text --commit-message Add",
+    Sent::Payload(r#"{"branch":"main","content":"text","commit_message":"Add"}"#),
+  ),
+  (
+    "/projects/{id}/repository/files/{file_path}/edit",
+    "--branch main --content text --commit-message Edit",
+    Sent::Payload(r#"{"branch":"main","content":"text","commit_message":"Edit"}"#),
+  ),
+  (
+    "/projects/{id}/repository/files/{file_path}/delete",
+    "--branch main --commit-message Delete",
+    Sent::Payload(r#"{"branch":"main","commit_message":"Delete"}"#),
+  ),
+  (
+    "/projects/{id}/repository/merge_base",
+    "--refs main",
+    Sent::Query(&[("refs[0]", "main")]),
+  ),
+  (
+    "/projects/{id}/runners/enable",
+    "--runner-id 9",
+    Sent::Payload(r#"{"runner_id":9}"#),
+  ),
+  (
+    "/projects/{id}/transfert",
+    "--namespace group",
+    Sent::Query(&[("namespace", "group")]),
+  ),
+  (
+    "/projects/{id}/variables/create",
+    "--key KEY --value value",
+    Sent::Payload(r#"{"key":"KEY","value":"value"}"#),
+  ),
+  (
+    "/projects/{id}/variables/{var_key}/update",
+    "--value value",
+    Sent::Payload(r#"{"value":"value"}"#),
+  ),
+  (
+    "/user/preferences/edit",
+    "--view-diffs-file-by-file",
+    Sent::Payload(r#"{"view_diffs_file_by_file":true}"#),
+  ),
+  (
+    "/user/status/edit",
+    "--emoji coffee",
+    Sent::Payload(r#"{"emoji":"coffee"}"#),
+  ),
+];
+
+/// Endpoint declared in the sources.
+#[derive(Debug, Default)]
+struct Endpoint {
+  /// Location of the declaration: `src/projects.rs:12`.
+  location: String,
+  route: String,
+  method: String,
+  cli_route: String,
+  payload_struct: Option<String>,
+  /// The payload is read from a file given by `--input`.
+  input_file: bool,
+  result_ok_status: String,
+  multiple_results: bool,
+}
+
+impl Endpoint {
+  /// Arguments of the command and path of its request. The ids are numbers given by
+  /// their position in the route.
+  fn command(&self) -> (Vec<String>, String) {
+    let mut args = vec![];
+    let mut path = self.route.clone();
+    let mut cli_route = String::new();
+    for (position, segment) in self
+      .cli_route
+      .split('/')
+      .filter(|s| !s.is_empty())
+      .enumerate()
+    {
+      match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+        Some(name) => {
+          let id = (100 + position).to_string();
+          path = path.replace(&format!("{{{name}}}"), &id);
+          args.push(id);
+        }
+        None => args.push(segment.to_string()),
+      }
+      cli_route.push_str(&format!("/{segment}"));
+      if let Some((_, required, _)) = required_args(&cli_route) {
+        args.extend(required.split_whitespace().map(str::to_string));
+      }
+    }
+    (args, path)
+  }
+
+  fn status(&self) -> u16 {
+    match self.result_ok_status.as_str() {
+      "CREATED" => 201,
+      "ACCEPTED" => 202,
+      "NO_CONTENT" => 204,
+      _ => 200,
+    }
+  }
+}
+
+fn required_args(cli_route: &str) -> Option<&'static (&'static str, &'static str, Sent)> {
+  REQUIRED_ARGS
+    .iter()
+    .find(|(route, _, _)| segments(route) == segments(cli_route))
+}
+
+/// Endpoints declared in the sources of the crate.
+fn endpoints() -> Vec<Endpoint> {
+  let mut files = vec![];
+  rust_files(
+    &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+    &mut files,
+  );
+  files.sort();
+  let mut endpoints = vec![];
+  let mut no_input_file = HashSet::new();
+  for file in files {
+    let source = std::fs::read_to_string(&file).unwrap();
+    let name = file
+      .strip_prefix(env!("CARGO_MANIFEST_DIR"))
+      .unwrap()
+      .display()
+      .to_string();
+    let tokens = tokenize(&source);
+    endpoints.extend(parse_endpoints(&name, &tokens));
+    no_input_file.extend(parse_no_input_file(&tokens));
+  }
+  for endpoint in &mut endpoints {
+    endpoint.input_file = endpoint
+      .payload_struct
+      .as_ref()
+      .is_some_and(|payload| !no_input_file.contains(payload));
+  }
+  endpoints
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+  for entry in std::fs::read_dir(dir).unwrap().flatten() {
+    let path = entry.path();
+    if path.is_dir() {
+      rust_files(&path, files);
+    } else if path.extension().is_some_and(|ext| ext == "rs") {
+      files.push(path);
+    }
+  }
+}
+
+/// Route without its empty segments: the segments of the commands.
+fn segments(route: &str) -> String {
+  route
+    .split('/')
+    .filter(|segment| !segment.is_empty())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Token of the sources: the comments are dropped.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Ident(String),
+  Str(String),
+  Punct(char),
+}
+
+/// Splits the source in tokens, with the line of each token.
+fn tokenize(source: &str) -> Vec<(Token, usize)> {
+  let chars: Vec<char> = source.chars().collect();
+  let mut tokens = vec![];
+  let mut line = 1;
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    match c {
+      '\n' => line += 1,
+      '/' if chars.get(i + 1) == Some(&'/') => {
+        while i < chars.len() && chars[i] != '\n' {
+          i += 1;
+        }
+        continue;
+      }
+      '"' => {
+        let start = line;
+        let mut value = String::new();
+        i += 1;
+        while i < chars.len() && chars[i] != '"' {
+          if chars[i] == '\\' {
+            i += 1;
+          }
+          if chars[i] == '\n' {
+            line += 1;
+          }
+          value.push(chars[i]);
+          i += 1;
+        }
+        tokens.push((Token::Str(value), start));
+      }
+      c if c.is_alphanumeric() || c == '_' => {
+        let mut ident = String::new();
+        while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+          ident.push(chars[i]);
+          i += 1;
+        }
+        tokens.push((Token::Ident(ident), line));
+        continue;
+      }
+      // Chars are dropped, lifetimes are kept as a quote.
+      '\'' if chars.get(i + 1) == Some(&'\\') => {
+        i += 2;
+        while i < chars.len() && chars[i] != '\'' {
+          i += 1;
+        }
+      }
+      '\'' if chars.get(i + 2) == Some(&'\'') => i += 2,
+      c if c.is_whitespace() => {}
+      c => tokens.push((Token::Punct(c), line)),
+    }
+    i += 1;
+  }
+  tokens
+}
+
+/// Endpoints declared in the `#[api(endpoint(...))]` attributes of the source.
+fn parse_endpoints(file: &str, tokens: &[(Token, usize)]) -> Vec<Endpoint> {
+  let mut endpoints = vec![];
+  let mut i = 0;
+  while i + 1 < tokens.len() {
+    if tokens[i].0 != Token::Ident("endpoint".into()) || tokens[i + 1].0 != Token::Punct('(') {
+      i += 1;
+      continue;
+    }
+    let mut endpoint = Endpoint {
+      location: format!("{file}:{}", tokens[i].1),
+      method: "GET".into(),
+      result_ok_status: "OK".into(),
+      ..Default::default()
+    };
+    i += 2;
+    let mut depth = 0;
+    while i < tokens.len() {
+      match &tokens[i].0 {
+        Token::Punct('(') => depth += 1,
+        Token::Punct(')') if depth == 0 => break,
+        Token::Punct(')') => depth -= 1,
+        Token::Ident(key) if depth == 0 => {
+          let value = match (tokens.get(i + 1), tokens.get(i + 2)) {
+            (Some((Token::Punct('='), _)), Some((Token::Str(value), _))) => Some(value.clone()),
+            _ => None,
+          };
+          match (key.as_str(), value) {
+            ("route", Some(value)) => endpoint.route = value,
+            ("method", Some(value)) => endpoint.method = value,
+            ("cli_route", Some(value)) => endpoint.cli_route = value,
+            ("payload_struct", Some(value)) => endpoint.payload_struct = Some(value),
+            ("result_ok_status", Some(value)) => endpoint.result_ok_status = value,
+            ("multiple_results", None) => endpoint.multiple_results = true,
+            _ => {}
+          }
+        }
+        _ => {}
+      }
+      i += 1;
+    }
+    endpoints.push(endpoint);
+  }
+  endpoints
+}
+
+/// Structs declared with `#[api(no_input_file)]`: their payload is given by the arguments.
+fn parse_no_input_file(tokens: &[(Token, usize)]) -> Vec<String> {
+  let mut structs = vec![];
+  let mut no_input_file = false;
+  for window in tokens.windows(2) {
+    match (&window[0].0, &window[1].0) {
+      (Token::Ident(ident), _) if ident == "no_input_file" => no_input_file = true,
+      (Token::Ident(ident), Token::Ident(name)) if ident == "struct" => {
+        if no_input_file {
+          structs.push(name.clone());
+        }
+        no_input_file = false;
+      }
+      _ => {}
+    }
+  }
+  structs
+}
+
+#[test]
+fn the_endpoints_are_declared() {
+  let endpoints = endpoints();
+  assert!(endpoints.len() > 300, "{} endpoints", endpoints.len());
+  let invalid: Vec<String> = endpoints
+    .iter()
+    .filter(|ep| ep.route.is_empty() || ep.cli_route.is_empty())
+    .map(|ep| ep.location.clone())
+    .collect();
+  assert!(invalid.is_empty(), "Endpoints without route: {invalid:#?}");
+}
+
+#[test]
+fn the_routes_have_no_empty_segment() {
+  let invalid: Vec<String> = endpoints()
+    .iter()
+    .filter(|ep| ep.route.contains("//") || ep.cli_route.contains("//"))
+    .map(|ep| format!("{}: {} -> {}", ep.location, ep.cli_route, ep.route))
+    .collect();
+  assert!(invalid.is_empty(), "Routes with `//`: {invalid:#?}");
+}
+
+#[test]
+fn the_cli_routes_are_unique() {
+  let endpoints = endpoints();
+  let mut duplicates = vec![];
+  for (i, ep) in endpoints.iter().enumerate() {
+    if let Some(other) = endpoints[..i]
+      .iter()
+      .find(|other| segments(&other.cli_route) == segments(&ep.cli_route))
+    {
+      duplicates.push(format!(
+        "{} and {}: {}",
+        other.location, ep.location, ep.cli_route
+      ));
+    }
+  }
+  assert!(
+    duplicates.is_empty(),
+    "Duplicated commands: {duplicates:#?}"
+  );
+}
+
+#[test]
+fn every_endpoint_sends_its_request() {
+  let stub = Stub::start();
+  let payload_file = stub.home().join("payload.json");
+  let mut failures = vec![];
+  for endpoint in endpoints() {
+    stub.reset();
+    let body = if endpoint.multiple_results {
+      "[]"
+    } else {
+      "{}"
+    };
+    stub.fallback(Response::new(endpoint.status(), body));
+    let (mut args, path) = endpoint.command();
+    let sent = required_args(&endpoint.cli_route).map_or(&Sent::Nothing, |(_, _, sent)| sent);
+    let mut payload = match sent {
+      Sent::Payload(payload) => Some(serde_json::from_str::<Value>(payload).unwrap()),
+      _ => None,
+    };
+
+    if endpoint.input_file {
+      let template_args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .chain(["--template"])
+        .collect();
+      let run = stub.run(&template_args);
+      if !run.success() {
+        failures.push(format!(
+          "{}: gitlab-cli {}: {}",
+          endpoint.location,
+          template_args.join(" "),
+          run.stderr()
+        ));
+        continue;
+      }
+      let template = run.stdout();
+      std::fs::write(&payload_file, &template).unwrap();
+      payload = Some(serde_json::from_str::<Value>(&template).unwrap());
+      args.extend(["--input".to_string(), payload_file.display().to_string()]);
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let run = stub.run(&args);
+    let command = format!("{}: gitlab-cli {}", endpoint.location, args.join(" "));
+    let requests = stub.requests();
+    let Some(request) = requests.first() else {
+      failures.push(format!("{command}: no request\n{}", run.stderr()));
+      continue;
+    };
+    if request.method != endpoint.method || request.path != path {
+      failures.push(format!(
+        "{command}: expected {} {path}, received {} {}",
+        endpoint.method, request.method, request.path
+      ));
+      continue;
+    }
+    if request.header("authorization") != Some(&format!("Bearer {TOKEN}")) {
+      failures.push(format!("{command}: the request is not authenticated"));
+    }
+    if let Sent::Query(pairs) = sent {
+      let query = request.query_pairs();
+      for (key, value) in pairs.iter() {
+        if !query.contains(&(key.to_string(), value.to_string())) {
+          failures.push(format!(
+            "{command}: expected {key}={value} in the query {:?}",
+            request.query
+          ));
+        }
+      }
+    }
+    if let Some(payload) = payload {
+      let sent = serde_json::from_slice::<Value>(&request.body).unwrap_or(Value::Null);
+      if without_nulls(sent.clone()) != without_nulls(payload.clone()) {
+        failures.push(format!(
+          "{command}: expected the payload {payload}, received {sent}"
+        ));
+      }
+    }
+  }
+  assert!(
+    failures.is_empty(),
+    "{} failures:\n{}",
+    failures.len(),
+    failures.join("\n")
+  );
+}
+
+/// The value without its `null` fields.
+fn without_nulls(value: Value) -> Value {
+  match value {
+    Value::Object(map) => Value::Object(
+      map
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| (key, without_nulls(value)))
+        .collect(),
+    ),
+    Value::Array(values) => Value::Array(values.into_iter().map(without_nulls).collect()),
+    value => value,
+  }
+}
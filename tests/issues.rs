@@ -0,0 +1,184 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+
+#[test]
+fn list_issues() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/4/issues",
+    Response::ok(format!("[{}]", fixture("issue"))),
+  );
+  let issues = stub
+    .run(&[
+      "projects",
+      "4",
+      "issues",
+      "--state",
+      "closed",
+      "--labels",
+      "foo,bar",
+      "--search",
+      "vero maxime",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(request.query_param("state").as_deref(), Some("closed"));
+  assert_eq!(request.query_param("labels").as_deref(), Some("foo,bar"));
+  assert_eq!(
+    request.query_param("search").as_deref(),
+    Some("vero maxime")
+  );
+  assert_eq!(issues[0]["iid"], 1);
+  assert_eq!(issues[0]["labels"][1], "bar");
+  assert_eq!(issues[0]["author"]["username"], "root");
+}
+
+#[test]
+fn list_issues_as_csv() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/4/issues",
+    Response::ok(format!(
+      "[{}]",
+      fixture("issue").replace(
+        "Omnis vero earum sunt corporis dolor et placeat.",
+        "Steps:\\n1. start"
+      )
+    )),
+  );
+  let output = stub
+    .run(&[
+      "projects",
+      "4",
+      "issues",
+      "-f",
+      "csv",
+      "--columns",
+      "iid,description",
+    ])
+    .ok();
+
+  assert_eq!(output, "iid,description\r\n1,\"Steps:\n1. start\"\r\n");
+}
+
+#[test]
+fn list_issues_with_a_template() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/4/issues",
+    Response::ok(format!("[{}]", fixture("issue"))),
+  );
+  let output = stub
+    .run(&[
+      "projects",
+      "4",
+      "issues",
+      "--output-template",
+      "{{ iid }} {{ state }}",
+    ])
+    .ok();
+
+  assert_eq!(output, "1 closed\n");
+}
+
+#[test]
+fn get_issue() {
+  let stub = Stub::start();
+  stub.on("GET", "/projects/4/issues/1", Response::fixture("issue"));
+  let issue = stub
+    .run(&["projects", "4", "issues", "1", "-f", "json"])
+    .json();
+
+  assert_eq!(issue["milestone"]["title"], "v3.0");
+  assert_eq!(issue["assignees"][0]["username"], "lennie");
+  assert_eq!(issue["references"]["full"], "my-group/my-project#1");
+}
+
+#[test]
+fn create_issue() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/4/issues",
+    Response::new(201, fixture("issue")),
+  );
+  stub
+    .run(&[
+      "projects",
+      "4",
+      "issues",
+      "create",
+      "--title",
+      "Consequatur vero maxime",
+      "--labels",
+      "foo,bar",
+      "--confidential",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["title"], "Consequatur vero maxime");
+  assert_eq!(payload["labels"], "foo,bar");
+  assert_eq!(payload["confidential"], true);
+}
+
+#[test]
+fn create_issue_with_a_template() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/4/issues",
+    Response::new(201, fixture("issue")),
+  );
+  let output = stub
+    .run(&[
+      "projects",
+      "4",
+      "issues",
+      "create",
+      "--title",
+      "Consequatur vero maxime",
+      "--output-template",
+      "#{{ iid }}",
+    ])
+    .ok();
+
+  assert_eq!(output, "#1\n");
+}
+
+#[test]
+fn create_issue_from_input_file() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/4/issues",
+    Response::new(201, fixture("issue")),
+  );
+  let input = stub.home().join("issue.json");
+  std::fs::write(&input, r#"{"title": "From a file", "weight": 3}"#).unwrap();
+  stub
+    .run(&[
+      "projects",
+      "4",
+      "issues",
+      "create",
+      "--input",
+      input.to_str().unwrap(),
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["title"], "From a file");
+  assert_eq!(payload["weight"], 3);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+
+#[test]
+fn list_jobs() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/1/jobs",
+    Response::ok(format!("[{}]", fixture("job"))),
+  );
+  let jobs = stub
+    .run(&["projects", "1", "jobs", "--per-page", "20", "-f", "json"])
+    .json();
+
+  assert_eq!(
+    stub.request().query_param("per_page").as_deref(),
+    Some("20")
+  );
+  assert_eq!(jobs[0]["name"], "teaspoon");
+  assert_eq!(jobs[0]["stage"], "test");
+}
+
+#[test]
+fn get_job() {
+  let stub = Stub::start();
+  stub.on("GET", "/projects/1/jobs/7", Response::fixture("job"));
+  let job = stub
+    .run(&["projects", "1", "jobs", "7", "-f", "json"])
+    .json();
+
+  assert_eq!(job["status"], "failed");
+  assert_eq!(job["pipeline"]["id"], 6);
+  assert_eq!(job["commit"]["short_id"], "0ff3ae19");
+  assert_eq!(job["artifacts"][2]["filename"], "job.log");
+}
+
+#[test]
+fn get_job_by_web_url() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/foo%2Fbar/jobs/7",
+    Response::fixture("job"),
+  );
+  stub
+    .run(&[
+      "projects",
+      "jobs",
+      &stub.web_url("foo/bar/-/jobs/7"),
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().path, "/projects/foo%2Fbar/jobs/7");
+}
+
+#[test]
+fn job_trace() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/1/jobs/7/trace",
+    Response::ok("Running with gitlab-runner 16.11.1\nJob succeeded\n"),
+  );
+  let trace = stub.run(&["projects", "1", "jobs", "7", "trace"]).ok();
+
+  assert!(trace.contains("Job succeeded"), "{trace}");
+}
+
+#[test]
+fn retry_job() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/1/jobs/7/retry",
+    Response::new(201, fixture("job")),
+  );
+  stub
+    .run(&["projects", "1", "jobs", "7", "retry", "-f", "json"])
+    .json();
+
+  assert_eq!(stub.request().method, "POST");
+}
@@ -0,0 +1,298 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+
+const APPROVALS: &str = r#"{
+  "approved": false,
+  "approvals_required": 2,
+  "approvals_left": 1,
+  "approved_by": [{
+    "user": {"id": 1, "username": "jdoe", "name": "John Doe", "state": "active", "web_url": ""}
+  }],
+  "approval_rules_left": [{"id": 7, "name": "Security", "rule_type": "regular"}],
+  "suggested_approvers": [],
+  "user_has_approved": false,
+  "user_can_approve": true
+}"#;
+
+/// The approvals of the merge request 133 of the fixture.
+fn on_approvals(stub: &Stub) {
+  stub.on(
+    "GET",
+    "/projects/15513260/merge_requests/133/approvals",
+    Response::ok(APPROVALS),
+  );
+}
+
+#[test]
+fn list_merge_requests() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/15513260/merge_requests",
+    Response::ok(format!("[{}]", fixture("merge_request"))),
+  );
+  let merge_requests = stub
+    .run(&[
+      "projects",
+      "15513260",
+      "merge_requests",
+      "--state",
+      "opened",
+      "--labels",
+      "backend,documentation",
+      "--target-branch",
+      "main",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(request.query_param("state").as_deref(), Some("opened"));
+  assert_eq!(
+    request.query_param("labels").as_deref(),
+    Some("backend,documentation")
+  );
+  assert_eq!(
+    request.query_param("target_branch").as_deref(),
+    Some("main")
+  );
+  assert_eq!(merge_requests[0]["iid"], 133);
+  assert_eq!(merge_requests[0]["author"]["username"], "marcel.amirault");
+}
+
+#[test]
+fn get_merge_request() {
+  let stub = Stub::start();
+  on_approvals(&stub);
+  stub.on(
+    "GET",
+    "/projects/15513260/merge_requests/133",
+    Response::fixture("merge_request"),
+  );
+  let merge_request = stub
+    .run(&[
+      "projects",
+      "15513260",
+      "merge_requests",
+      "133",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(merge_request["title"], "Manual job rules");
+  assert_eq!(merge_request["head_pipeline"]["status"], "failed");
+  assert_eq!(
+    merge_request["diff_refs"]["head_sha"],
+    "e82eb4a098e32c796079ca3915e07487fc4db24c"
+  );
+  assert_eq!(merge_request["approvals"]["approvals_left"], 1);
+  assert_eq!(
+    merge_request["approvals"]["approved_by"][0]["user"]["username"],
+    "jdoe"
+  );
+  assert_eq!(
+    stub.requests()[1].path,
+    "/projects/15513260/merge_requests/133/approvals"
+  );
+}
+
+#[test]
+fn list_merge_requests_with_their_approvals() {
+  let stub = Stub::start();
+  on_approvals(&stub);
+  stub.on(
+    "GET",
+    "/projects/15513260/merge_requests",
+    Response::ok(format!("[{}]", fixture("merge_request"))),
+  );
+  let merge_requests = stub
+    .run(&[
+      "projects",
+      "15513260",
+      "merge_requests",
+      "--approvals",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.requests()[0].query_param("approvals"), None);
+  assert_eq!(merge_requests[0]["approval"]["approved"], false);
+  assert_eq!(merge_requests[0]["approval"]["approved_by"], "jdoe");
+  assert_eq!(merge_requests[0]["approval"]["approvals_left"], 1);
+  assert_eq!(merge_requests[0]["approval"]["rules_left"], "Security");
+
+  let table = stub
+    .run(&["projects", "15513260", "merge_requests", "--approvals"])
+    .ok();
+  assert!(table.contains("approval.approved_by"), "{table}");
+  assert!(table.contains("Security"), "{table}");
+}
+
+#[test]
+fn list_merge_requests_without_their_approvals() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/15513260/merge_requests",
+    Response::ok(format!("[{}]", fixture("merge_request"))),
+  );
+  let merge_requests = stub
+    .run(&["projects", "15513260", "merge_requests", "-f", "json"])
+    .json();
+
+  assert_eq!(stub.requests().len(), 1);
+  assert!(merge_requests[0].get("approval").is_none());
+}
+
+#[test]
+fn get_merge_request_by_web_url() {
+  let stub = Stub::start();
+  on_approvals(&stub);
+  stub.on(
+    "GET",
+    "/projects/marcel.amirault%2Ftest-project/merge_requests/133",
+    Response::fixture("merge_request"),
+  );
+  stub
+    .run(&[
+      "projects",
+      "merge_requests",
+      &stub.web_url("marcel.amirault/test-project/-/merge_requests/133"),
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(
+    stub.requests()[0].path,
+    "/projects/marcel.amirault%2Ftest-project/merge_requests/133"
+  );
+}
+
+#[test]
+fn get_merge_request_of_the_current_branch() {
+  let stub = Stub::start();
+  on_approvals(&stub);
+  stub
+    .on(
+      "GET",
+      "/projects/group%2Fproject/merge_requests",
+      Response::ok(r#"[{"iid": 133}]"#),
+    )
+    .on(
+      "GET",
+      "/projects/group%2Fproject/merge_requests/133",
+      Response::fixture("merge_request"),
+    );
+  for args in [
+    &["init", "--quiet", "--initial-branch", "manual-job-rules"][..],
+    &[
+      "remote",
+      "add",
+      "origin",
+      "git@gitlab.example.com:group/project.git",
+    ],
+  ] {
+    let status = std::process::Command::new("git")
+      .args(args)
+      .current_dir(stub.home())
+      .status()
+      .unwrap();
+    assert!(status.success());
+  }
+  stub
+    .run(&["projects", ".", "merge_requests", ".", "-f", "json"])
+    .json();
+
+  let requests = stub.requests();
+  assert_eq!(
+    requests[0].query_param("source_branch").as_deref(),
+    Some("manual-job-rules")
+  );
+  assert_eq!(
+    requests[1].path,
+    "/projects/group%2Fproject/merge_requests/133"
+  );
+}
+
+#[test]
+fn current_project_outside_of_a_working_tree() {
+  let stub = Stub::start();
+  let run = stub.run(&["projects", ".", "merge_requests", "."]);
+
+  assert!(!run.success());
+  assert!(
+    run.stderr().contains("no `origin` remote"),
+    "{}",
+    run.stderr()
+  );
+  assert!(stub.requests().is_empty());
+
+  // The help is printed without resolving the ids.
+  stub
+    .run(&["projects", ".", "merge_requests", ".", "--help"])
+    .ok();
+}
+
+#[test]
+fn create_merge_request() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/15513260/merge_requests",
+    Response::new(201, fixture("merge_request")),
+  );
+  stub
+    .run(&[
+      "projects",
+      "15513260",
+      "merge_requests",
+      "create",
+      "--source-branch",
+      "manual-job-rules",
+      "--target-branch",
+      "main",
+      "--title",
+      "Manual job rules",
+      "--remove-source-branch",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["source_branch"], "manual-job-rules");
+  assert_eq!(payload["target_branch"], "main");
+  assert_eq!(payload["title"], "Manual job rules");
+  assert_eq!(payload["remove_source_branch"], true);
+}
+
+#[test]
+fn merge_merge_request() {
+  let stub = Stub::start();
+  stub.on(
+    "PUT",
+    "/projects/15513260/merge_requests/133/merge",
+    Response::fixture("merge_request"),
+  );
+  stub
+    .run(&[
+      "projects",
+      "15513260",
+      "merge_requests",
+      "133",
+      "merge",
+      "--squash",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(request.method, "PUT");
+  assert_eq!(request.json()["squash"], true);
+}
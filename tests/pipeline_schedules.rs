@@ -0,0 +1,93 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+
+#[test]
+fn list_pipeline_schedules() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/29/pipeline_schedules",
+    Response::ok(format!("[{}]", fixture("pipeline_schedule"))),
+  );
+  let schedules = stub
+    .run(&[
+      "projects",
+      "29",
+      "pipeline_schedules",
+      "--scope",
+      "active",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(
+    stub.request().query_param("scope").as_deref(),
+    Some("active")
+  );
+  assert_eq!(schedules[0]["cron"], "0 2 * * *");
+  assert_eq!(schedules[0]["last_pipeline"]["id"], 332);
+  assert_eq!(schedules[0]["last_pipeline"]["status"], "pending");
+}
+
+#[test]
+fn get_pipeline_schedule() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/29/pipeline_schedules/13",
+    Response::fixture("pipeline_schedule"),
+  );
+  let schedule = stub
+    .run(&["projects", "29", "pipeline_schedules", "13", "-f", "json"])
+    .json();
+
+  assert_eq!(schedule["owner"]["username"], "root");
+  assert_eq!(schedule["last_pipeline"]["ref"], "refs/heads/main");
+  assert_eq!(schedule["variables"][0]["key"], "TEST_VARIABLE_1");
+}
+
+#[test]
+fn deactivate_pipeline_schedule() {
+  let stub = Stub::start();
+  stub.on(
+    "PUT",
+    "/projects/29/pipeline_schedules/13",
+    Response::fixture("pipeline_schedule"),
+  );
+  stub
+    .run(&[
+      "projects",
+      "29",
+      "pipeline_schedules",
+      "13",
+      "edit",
+      "--cron",
+      "0 3 * * *",
+      "--active",
+      "false",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["cron"], "0 3 * * *");
+  assert_eq!(payload["active"], "false");
+}
+
+#[test]
+fn play_pipeline_schedule() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/29/pipeline_schedules/13/play",
+    Response::new(201, r#"{"message":"201 Created"}"#),
+  );
+  stub
+    .run(&["projects", "29", "pipeline_schedules", "13", "play"])
+    .ok();
+
+  assert_eq!(stub.request().method, "POST");
+}
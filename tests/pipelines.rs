@@ -0,0 +1,160 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+
+#[test]
+fn list_pipelines() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  let pipelines = stub
+    .run(&[
+      "projects",
+      "21",
+      "pipelines",
+      "--status",
+      "success",
+      "--ref",
+      "main",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(request.query_param("status").as_deref(), Some("success"));
+  assert_eq!(request.query_param("ref").as_deref(), Some("main"));
+  assert_eq!(pipelines[0]["id"], 287);
+  assert_eq!(pipelines[0]["ref"], "main");
+}
+
+#[test]
+fn get_pipeline() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines/287",
+    Response::fixture("pipeline"),
+  );
+  let pipeline = stub
+    .run(&["projects", "21", "pipelines", "287", "-f", "json"])
+    .json();
+
+  assert_eq!(pipeline["sha"], "50f0acb76a40e34a4ff304f7347dcc6587da8a14");
+  assert_eq!(pipeline["user"]["username"], "root");
+  assert_eq!(pipeline["detailed_status"]["text"], "passed");
+}
+
+#[test]
+fn create_pipeline() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/21/pipeline",
+    Response::new(201, fixture("pipeline")),
+  );
+  stub
+    .run(&[
+      "projects",
+      "21",
+      "pipelines",
+      "create",
+      "--ref",
+      "main",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().json()["ref"], "main");
+}
+
+#[test]
+fn watch_pipeline() {
+  let stub = Stub::start();
+  let next = format!(
+    "<{}/projects/21/pipelines/287/jobs?per_page=100&page=2>; rel=\"next\"",
+    stub.base_url()
+  );
+  stub
+    .on(
+      "GET",
+      "/projects/21/pipelines/287",
+      Response::ok(fixture("pipeline").replacen(
+        r#""status": "success""#,
+        r#""status": "failed""#,
+        1,
+      )),
+    )
+    .on(
+      "GET",
+      "/projects/21/pipelines/287/jobs",
+      Response::ok(r#"[{"id": 1, "name": "build", "stage": "build", "status": "success"}]"#)
+        .header("link", &next),
+    )
+    .on(
+      "GET",
+      "/projects/21/pipelines/287/jobs",
+      Response::ok(r#"[{"id": 2, "name": "test", "stage": "test", "status": "failed"}]"#),
+    )
+    .on(
+      "GET",
+      "/projects/21/pipelines/287/bridges",
+      Response::ok("[]"),
+    );
+  let run = stub.run(&["projects", "21", "pipelines", "287", "watch"]);
+
+  // The exit code is given by the status of the pipeline.
+  assert_eq!(run.0.status.code(), Some(1));
+  let output = run.stdout();
+  assert!(output.contains("build"), "{output}");
+  assert!(output.contains("test"), "{output}");
+  let pages: Vec<Option<String>> = stub
+    .requests()
+    .iter()
+    .filter(|request| request.path.ends_with("/jobs"))
+    .map(|request| request.query_param("page"))
+    .collect();
+  assert_eq!(pages, [None, Some("2".to_string())]);
+}
+
+#[test]
+fn all_pages() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  let output = stub
+    .run(&["projects", "21", "pipelines", "--all", "--per-page", "1"])
+    .ok();
+
+  // The first page is full: the next one is asked.
+  let requests = stub.requests();
+  assert_eq!(requests.len(), 2);
+  assert_eq!(requests[0].query_param("per_page").as_deref(), Some("1"));
+  assert_eq!(requests[1].query_param("page").as_deref(), Some("2"));
+  assert_eq!(requests[1].query_param("per_page").as_deref(), Some("1"));
+  assert_eq!(output.lines().count(), 2);
+}
+
+#[test]
+fn max_items() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{0}, {0}]", fixture("pipeline"))),
+  );
+  let output = stub
+    .run(&["projects", "21", "pipelines", "--max-items", "1"])
+    .ok();
+
+  let request = stub.request();
+  assert_eq!(request.query_param("per_page").as_deref(), Some("100"));
+  assert_eq!(output.lines().count(), 1);
+}
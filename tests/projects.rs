@@ -0,0 +1,220 @@
+mod common;
+
+use common::{fixture, Response, Stub, TOKEN};
+
+#[test]
+fn list_projects() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects",
+    Response::ok(format!("[{}]", fixture("project"))),
+  );
+  let projects = stub
+    .run(&[
+      "projects",
+      "--membership",
+      "--order-by",
+      "updated_at",
+      "--per-page",
+      "2",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(request.method, "GET");
+  assert_eq!(request.query_param("membership").as_deref(), Some("true"));
+  assert_eq!(
+    request.query_param("order_by").as_deref(),
+    Some("updated_at")
+  );
+  assert_eq!(request.query_param("per_page").as_deref(), Some("2"));
+  assert_eq!(
+    request.header("authorization"),
+    Some(format!("Bearer {TOKEN}").as_str())
+  );
+  assert_eq!(projects[0]["id"], 3);
+  assert_eq!(
+    projects[0]["path_with_namespace"],
+    "diaspora/diaspora-project-site"
+  );
+}
+
+#[test]
+fn keyset_pages() {
+  let stub = Stub::start();
+  let page = format!("[{}]", fixture("project"));
+  let next = format!(
+    "<{}/projects?id_after=3&membership=true&order_by=id&pagination=keyset&per_page=100&sort=asc>; \
+     rel=\"next\"",
+    stub.base_url()
+  );
+  stub
+    .on("GET", "/projects", Response::ok(page.clone()))
+    .on(
+      "GET",
+      "/projects",
+      Response::ok(page.clone()).header("link", &next),
+    )
+    .on("GET", "/projects", Response::ok(page));
+  let output = stub.run(&["projects", "--membership", "--all"]).ok();
+
+  // The first page is asked again with the keyset pagination, then the next one by its link.
+  let requests = stub.requests();
+  assert_eq!(requests.len(), 3);
+  assert_eq!(
+    requests[1].query_param("pagination").as_deref(),
+    Some("keyset")
+  );
+  assert_eq!(requests[1].query_param("order_by").as_deref(), Some("id"));
+  assert_eq!(requests[1].query_param("sort").as_deref(), Some("asc"));
+  assert_eq!(
+    requests[1].query_param("membership").as_deref(),
+    Some("true")
+  );
+  assert_eq!(requests[1].query_param("page"), None);
+  assert_eq!(requests[2].query_param("id_after").as_deref(), Some("3"));
+  assert_eq!(requests[2].query_param("page"), None);
+  assert_eq!(output.lines().count(), 2);
+}
+
+#[test]
+fn ordered_pages() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects",
+    Response::ok(format!("[{}]", fixture("project"))),
+  );
+  stub
+    .run(&["projects", "--order-by", "name", "--all", "--per-page", "1"])
+    .ok();
+
+  // The lists ordered by another field than `id` are followed by their offset.
+  let requests = stub.requests();
+  assert_eq!(requests.len(), 2);
+  assert_eq!(requests[1].query_param("page").as_deref(), Some("2"));
+  assert_eq!(requests[1].query_param("pagination"), None);
+}
+
+#[test]
+fn get_project() {
+  let stub = Stub::start();
+  stub.on("GET", "/projects/3", Response::fixture("project"));
+  let project = stub.run(&["projects", "3", "-f", "json"]).json();
+
+  assert_eq!(stub.request().path, "/projects/3");
+  assert_eq!(
+    project["name_with_namespace"],
+    "Diaspora / Diaspora Project Site"
+  );
+  assert_eq!(project["default_branch"], "main");
+  assert_eq!(project["shared_with_groups"][0]["group_name"], "Twitter");
+}
+
+#[test]
+fn get_project_by_path() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/diaspora%2Fdiaspora-project-site",
+    Response::fixture("project"),
+  );
+  stub
+    .run(&["projects", "diaspora/diaspora-project-site", "-f", "json"])
+    .json();
+
+  assert_eq!(
+    stub.request().path,
+    "/projects/diaspora%2Fdiaspora-project-site"
+  );
+}
+
+#[test]
+fn get_project_by_web_url() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/group%2Fsub%2Fproject/pipelines",
+    Response::ok("[]"),
+  );
+  stub
+    .run(&[
+      "projects",
+      &stub.web_url("group/sub/project"),
+      "pipelines",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(
+    stub.request().path,
+    "/projects/group%2Fsub%2Fproject/pipelines"
+  );
+}
+
+#[test]
+fn web_url_of_another_host() {
+  let stub = Stub::start();
+  let run = stub.run(&[
+    "projects",
+    "https://gitlab.example.com/group/sub/project",
+    "pipelines",
+  ]);
+
+  assert!(!run.success());
+  assert!(run.stderr().contains("is a web URL of"), "{}", run.stderr());
+  assert!(stub.requests().is_empty());
+}
+
+#[test]
+fn create_project() {
+  let stub = Stub::start();
+  stub.on("POST", "/projects", Response::new(201, fixture("project")));
+  stub
+    .run(&[
+      "projects",
+      "create",
+      "--name",
+      "Diaspora Project Site",
+      "--visibility",
+      "private",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(
+    request.header("content-type"),
+    Some("application/json; charset=UTF-8")
+  );
+  assert_eq!(request.json()["name"], "Diaspora Project Site");
+  assert_eq!(request.json()["visibility"], "private");
+}
+
+#[test]
+fn delete_project() {
+  let stub = Stub::start();
+  stub.on("DELETE", "/projects/3", Response::new(202, ""));
+  stub.run(&["projects", "3", "delete"]).ok();
+
+  assert_eq!(stub.request().method, "DELETE");
+}
+
+#[test]
+fn unexpected_status() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/3",
+    Response::new(403, r#"{"message":"403 Forbidden"}"#),
+  );
+  let run = stub.run(&["projects", "3", "-f", "json"]);
+
+  assert!(!run.success());
+  assert!(run.stderr().contains("403"), "{}", run.stderr());
+}
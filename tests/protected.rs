@@ -0,0 +1,198 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+use serde_json::{json, Value};
+
+/// The fixture `branch` named `name`.
+fn branch(name: &str, protected: bool) -> Value {
+  let mut branch: Value = serde_json::from_str(&fixture("branch")).unwrap();
+  branch["name"] = json!(name);
+  branch["protected"] = json!(protected);
+  branch["default"] = json!(name == "main");
+  branch
+}
+
+/// The fixture `protected_branch` named `name`.
+fn protected_branch(name: &str, allow_force_push: bool) -> Value {
+  let mut rule: Value = serde_json::from_str(&fixture("protected_branch")).unwrap();
+  rule["name"] = json!(name);
+  rule["allow_force_push"] = json!(allow_force_push);
+  rule
+}
+
+fn stub_branches() -> Stub {
+  let stub = Stub::start();
+  stub
+    .on(
+      "GET",
+      "/projects/29/repository/branches",
+      Response::ok(
+        json!([
+          branch("main", true),
+          branch("release/1.0", true),
+          branch("feature", false)
+        ])
+        .to_string(),
+      ),
+    )
+    .on(
+      "GET",
+      "/projects/29/protected_branches",
+      Response::ok(
+        json!([
+          protected_branch("release/*", true),
+          protected_branch("main", false)
+        ])
+        .to_string(),
+      ),
+    );
+  stub
+}
+
+#[test]
+fn list_protected_branches() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/29/protected_branches",
+    Response::ok(format!("[{}]", fixture("protected_branch"))),
+  );
+  let rules = stub
+    .run(&[
+      "projects",
+      "29",
+      "protected_branches",
+      "--search",
+      "ma",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().query_param("search").as_deref(), Some("ma"));
+  assert_eq!(rules[0]["name"], "main");
+  assert_eq!(
+    rules[0]["push_access_levels"][0]["access_level_description"],
+    "Maintainers"
+  );
+}
+
+#[test]
+fn protect_branch() {
+  let stub = Stub::start();
+  stub.on(
+    "POST",
+    "/projects/29/protected_branches",
+    Response::new(201, fixture("protected_branch")),
+  );
+  stub
+    .run(&[
+      "projects",
+      "29",
+      "protected_branches",
+      "protect",
+      "--name",
+      "release/*",
+      "--push-access-level",
+      "0",
+      "--merge-access-level",
+      "40",
+      "--allow-force-push",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["name"], "release/*");
+  assert_eq!(payload["push_access_level"], "0");
+  assert_eq!(payload["merge_access_level"], "40");
+  assert_eq!(payload["allow_force_push"], true);
+}
+
+#[test]
+fn disallow_force_push() {
+  let stub = Stub::start();
+  stub.on(
+    "PATCH",
+    "/projects/29/protected_branches/main",
+    Response::fixture("protected_branch"),
+  );
+  stub
+    .run(&[
+      "projects",
+      "29",
+      "protected_branches",
+      "main",
+      "update",
+      "--allow-force-push",
+      "false",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(stub.request().json(), json!({"allow_force_push": "false"}));
+}
+
+#[test]
+fn unprotect_tag() {
+  let stub = Stub::start();
+  stub.on(
+    "DELETE",
+    "/projects/29/protected_tags/v1.0",
+    Response::new(204, ""),
+  );
+  stub
+    .run(&["projects", "29", "protected_tags", "v1.0", "unprotect"])
+    .ok();
+
+  assert_eq!(stub.request().method, "DELETE");
+}
+
+#[test]
+fn list_branches_with_their_protection() {
+  let stub = stub_branches();
+  let branches = stub
+    .run(&[
+      "projects",
+      "29",
+      "repository",
+      "branches",
+      "--protected",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  assert_eq!(branches[0]["protection"]["rule"], "main");
+  assert_eq!(branches[0]["protection"]["push"], "Maintainers");
+  assert_eq!(branches[0]["protection"]["allow_force_push"], false);
+  assert_eq!(branches[1]["protection"]["rule"], "release/*");
+  assert_eq!(branches[1]["protection"]["merge"], "Example Merge Group");
+  assert_eq!(branches[1]["protection"]["allow_force_push"], true);
+  assert!(branches[2].get("protection").is_none());
+}
+
+#[test]
+fn branches_table_shows_their_protection() {
+  let stub = stub_branches();
+  let table = stub
+    .run(&["projects", "29", "repository", "branches", "--protected"])
+    .ok();
+
+  assert!(table.contains("protection.push"), "{table}");
+  assert!(table.contains("release/*"), "{table}");
+  assert!(table.contains("Maintainers"), "{table}");
+}
+
+#[test]
+fn list_branches_without_their_protection() {
+  let stub = stub_branches();
+  let branches = stub
+    .run(&["projects", "29", "repository", "branches", "-f", "json"])
+    .json();
+
+  assert_eq!(stub.requests().len(), 1);
+  assert!(branches[0].get("protection").is_none());
+}
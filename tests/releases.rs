@@ -0,0 +1,103 @@
+mod common;
+
+use common::{Response, Stub};
+
+const RELEASE: &str = r###"{
+  "tag_name": "v1.1.0",
+  "name": "v1.1.0",
+  "description": "## 1.1.0 (2023-05-04)\n\n### Features\n\n- Add the releases",
+  "created_at": "2023-05-04T10:00:00.000Z"
+}"###;
+
+#[test]
+fn create_release_from_changelog() {
+  let stub = Stub::start();
+  stub
+    .on(
+      "GET",
+      "/projects/5/repository/changelog",
+      Response::ok(r###"{"notes": "## 1.1.0 (2023-05-04)\n\n### Features\n\n- Add the releases"}"###),
+    )
+    .on("POST", "/projects/5/releases", Response::new(201, RELEASE));
+  let output = stub
+    .run(&[
+      "projects",
+      "5",
+      "releases",
+      "create_from_changelog",
+      "--tag-name",
+      "v1.1.0",
+      "--version",
+      "1.1.0",
+      "--from",
+      "v1.0.0",
+    ])
+    .ok();
+
+  let requests = stub.requests();
+  assert_eq!(requests[0].query_param("version").as_deref(), Some("1.1.0"));
+  assert_eq!(requests[0].query_param("from").as_deref(), Some("v1.0.0"));
+  let payload = requests[1].json();
+  assert_eq!(payload["tag_name"], "v1.1.0");
+  assert_eq!(
+    payload["description"],
+    "## 1.1.0 (2023-05-04)\n\n### Features\n\n- Add the releases"
+  );
+  assert!(output.contains("Created the release v1.1.0"), "{output}");
+}
+
+#[test]
+fn create_release_from_an_empty_changelog() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/changelog",
+    Response::ok(r#"{"notes": ""}"#),
+  );
+  let run = stub.run(&[
+    "projects",
+    "5",
+    "releases",
+    "create_from_changelog",
+    "--tag-name",
+    "v1.1.0",
+    "--version",
+    "1.1.0",
+  ]);
+
+  assert!(!run.success());
+  assert!(run.stderr().contains("is empty"), "{}", run.stderr());
+  assert_eq!(stub.requests().len(), 1);
+}
+
+#[test]
+fn description_before_the_changelog() {
+  let stub = Stub::start();
+  stub
+    .on(
+      "GET",
+      "/projects/5/repository/changelog",
+      Response::ok(r#"{"notes": "- Add the releases"}"#),
+    )
+    .on("POST", "/projects/5/releases", Response::new(201, RELEASE));
+  stub
+    .run(&[
+      "projects",
+      "5",
+      "releases",
+      "create_from_changelog",
+      "--tag-name",
+      "v1.1.0",
+      "--description",
+      "First release.",
+      "--version",
+      "1.1.0",
+    ])
+    .ok();
+
+  let payload = stub.requests()[1].json();
+  assert_eq!(
+    payload["description"],
+    "First release.\n\n- Add the releases"
+  );
+}
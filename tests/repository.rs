@@ -0,0 +1,162 @@
+mod common;
+
+use common::{Response, Stub};
+
+const TREE: &str = r#"[
+  {"id": "a1e8f8d745cc87e3a9248358d9352bb7f9a0aeba", "name": "nginx", "type": "tree", "path": "config/nginx", "mode": "040000"},
+  {"id": "b2f7c6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b0a9", "name": "app.toml", "type": "blob", "path": "config/app.toml", "mode": "100644"},
+  {"id": "c3a8b7f6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b0", "name": "reload.sh", "type": "blob", "path": "config/nginx/reload.sh", "mode": "100755"}
+]"#;
+
+#[test]
+fn download_archive() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/archive.tar.gz",
+    Response::ok("archive content"),
+  );
+  let output = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "archive",
+      "--sha",
+      "v1.0.0",
+      "--path",
+      "config",
+    ])
+    .ok();
+
+  let request = stub.request();
+  assert_eq!(request.query_param("sha").as_deref(), Some("v1.0.0"));
+  assert_eq!(request.query_param("path").as_deref(), Some("config"));
+  assert_eq!(output, "archive content");
+}
+
+#[test]
+fn download_archive_in_a_format() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/archive.zip",
+    Response::ok("zip content"),
+  );
+  let output = stub
+    .run(&["projects", "5", "repository", "archive", "zip"])
+    .ok();
+
+  assert_eq!(output, "zip content");
+}
+
+#[test]
+fn download_tree() {
+  let stub = Stub::start();
+  stub.on("GET", "/projects/5/repository/tree", Response::ok(TREE));
+  stub.on(
+    "GET",
+    "/projects/5/repository/blobs/b2f7c6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b0a9/raw",
+    Response::ok("port = 8080\n"),
+  );
+  stub.on(
+    "GET",
+    "/projects/5/repository/blobs/c3a8b7f6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b0/raw",
+    Response::ok("#!/bin/sh\nnginx -s reload\n"),
+  );
+  let dir = stub.home().join("vendor");
+  let output = stub
+    .run(&[
+      "projects",
+      "5",
+      "repository",
+      "tree",
+      "--path",
+      "config",
+      "--ref",
+      "main",
+      "--download",
+      dir.to_str().unwrap(),
+    ])
+    .ok();
+
+  let listing = &stub.requests()[0];
+  assert_eq!(listing.query_param("path").as_deref(), Some("config"));
+  assert_eq!(listing.query_param("ref").as_deref(), Some("main"));
+  assert_eq!(listing.query_param("recursive").as_deref(), Some("true"));
+  assert_eq!(listing.query_param("page").as_deref(), Some("1"));
+  assert_eq!(stub.requests().len(), 3);
+  assert!(output.contains("Downloaded 2 files"), "{output}");
+  assert_eq!(
+    std::fs::read_to_string(dir.join("app.toml")).unwrap(),
+    "port = 8080\n"
+  );
+  let script = dir.join("nginx/reload.sh");
+  assert_eq!(
+    std::fs::read_to_string(&script).unwrap(),
+    "#!/bin/sh\nnginx -s reload\n"
+  );
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = std::fs::metadata(&script).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o755);
+  }
+}
+
+#[test]
+fn download_tree_outside_of_the_directory() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/5/repository/tree",
+    Response::ok(
+      r#"[{"id": "b2f7c6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b0a9", "name": "passwd", "type": "blob", "path": "../passwd", "mode": "100644"}]"#,
+    ),
+  );
+  let dir = stub.home().join("vendor");
+  let run = stub.run(&[
+    "projects",
+    "5",
+    "repository",
+    "tree",
+    "--download",
+    dir.to_str().unwrap(),
+  ]);
+
+  assert!(!run.success());
+  assert!(run.stderr().contains("not a path inside the repository"));
+  assert!(!stub.home().join("passwd").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn download_tree_through_a_symbolic_link() {
+  let stub = Stub::start();
+  stub.on("GET", "/projects/5/repository/tree", Response::ok(TREE));
+  stub.fallback(Response::ok("content"));
+  let dir = stub.home().join("vendor");
+  let outside = stub.home().join("outside");
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::create_dir(&outside).unwrap();
+  // Left by a previous download.
+  std::os::unix::fs::symlink(&outside, dir.join("nginx")).unwrap();
+  let run = stub.run(&[
+    "projects",
+    "5",
+    "repository",
+    "tree",
+    "--path",
+    "config",
+    "--download",
+    dir.to_str().unwrap(),
+  ]);
+
+  assert!(!run.success());
+  assert!(
+    run.stderr().contains("is a symbolic link"),
+    "{}",
+    run.stderr()
+  );
+  assert_eq!(std::fs::read_dir(&outside).unwrap().count(), 0);
+}
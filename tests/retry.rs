@@ -0,0 +1,143 @@
+mod common;
+
+use common::{fixture, Response, Stub, TOKEN};
+
+#[test]
+fn retry_after_rate_limit() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::new(429, r#"{"message":"Retry later"}"#).header("retry-after", "0"),
+  );
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  let output = stub
+    .run(&["projects", "21", "pipelines", "--all", "--per-page", "1"])
+    .ok();
+
+  assert_eq!(stub.requests().len(), 3);
+  assert_eq!(output.lines().count(), 2, "{output}");
+}
+
+#[test]
+fn retry_when_unavailable() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  stub.on("GET", "/projects/21/pipelines", Response::new(503, ""));
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  // The backoff is at most 1s for the first retry.
+  let output = stub
+    .run(&["projects", "21", "pipelines", "--all", "--per-page", "1"])
+    .ok();
+
+  assert_eq!(stub.requests().len(), 3);
+  assert_eq!(output.lines().count(), 2, "{output}");
+}
+
+#[test]
+fn no_retry() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/21/pipelines",
+    Response::ok(format!("[{}]", fixture("pipeline"))),
+  );
+  stub.on("GET", "/projects/21/pipelines", Response::new(503, ""));
+  let run = stub.run(&[
+    "projects",
+    "21",
+    "pipelines",
+    "--all",
+    "--per-page",
+    "1",
+    "--max-retries",
+    "0",
+  ]);
+
+  assert!(!run.success());
+  assert_eq!(stub.requests().len(), 2);
+}
+
+#[test]
+fn no_retry_of_a_post() {
+  let stub = Stub::start();
+  stub
+    .on(
+      "HEAD",
+      "/projects/5/repository/files/README.md",
+      Response::ok(""),
+    )
+    .on(
+      "POST",
+      "/projects/5/repository/commits",
+      Response::new(429, r#"{"message":"Retry later"}"#).header("retry-after", "0"),
+    );
+  std::fs::write(stub.home().join("README.md"), "# Project\n").unwrap();
+  let run = stub.run(&[
+    "projects",
+    "5",
+    "repository",
+    "commits",
+    "create",
+    "--branch",
+    "main",
+    "--commit-message",
+    "Update the README",
+    "--path",
+    "README.md",
+  ]);
+
+  assert!(!run.success());
+  let posts = stub
+    .requests()
+    .into_iter()
+    .filter(|request| request.method == "POST")
+    .count();
+  assert_eq!(posts, 1);
+}
+
+#[test]
+fn retry_of_a_generated_command() {
+  let stub = Stub::start();
+  stub
+    .on(
+      "GET",
+      "/projects",
+      Response::new(429, r#"{"message":"Retry later"}"#).header("retry-after", "0"),
+    )
+    .on(
+      "GET",
+      "/projects",
+      Response::ok(format!("[{}]", fixture("project"))),
+    );
+  let projects = stub.run(&["projects", "--membership", "-f", "json"]).json();
+
+  let requests = stub.requests();
+  assert_eq!(requests.len(), 2);
+  assert_eq!(
+    requests[1].query_param("membership").as_deref(),
+    Some("true")
+  );
+  assert_eq!(
+    requests[1].header("authorization"),
+    Some(format!("Bearer {TOKEN}").as_str())
+  );
+  assert_eq!(projects[0]["id"], 3);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::{fixture, Response, Stub};
+
+#[test]
+fn current_user() {
+  let stub = Stub::start();
+  stub.on("GET", "/user", Response::fixture("user"));
+  let user = stub.run(&["user", "-f", "json"]).json();
+
+  assert_eq!(user["username"], "john_smith");
+  assert_eq!(user["job_title"], "Operations Specialist");
+}
+
+#[test]
+fn list_users() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/users",
+    Response::ok(format!("[{}]", fixture("user"))),
+  );
+  let users = stub
+    .run(&[
+      "users",
+      "--username",
+      "john_smith",
+      "--active",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let request = stub.request();
+  assert_eq!(
+    request.query_param("username").as_deref(),
+    Some("john_smith")
+  );
+  assert_eq!(request.query_param("active").as_deref(), Some("true"));
+  assert_eq!(users[0]["id"], 1);
+}
+
+#[test]
+fn user_without_avatar() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/users/1",
+    Response::ok(fixture("user").replace(
+      r#""https://gitlab.example.com/uploads/-/system/user/avatar/1/avatar.png""#,
+      "null",
+    )),
+  );
+  let user = stub.run(&["users", "1", "-f", "json"]).json();
+
+  assert_eq!(user["username"], "john_smith");
+  assert!(user.get("avatar_url").is_none());
+}
@@ -0,0 +1,271 @@
+mod common;
+
+use common::{Response, Stub};
+
+const VARIABLE: &str = r#"{
+  "key": "TEST_VARIABLE_1",
+  "variable_type": "env_var",
+  "value": "TEST_1",
+  "protected": false,
+  "masked": true,
+  "raw": false,
+  "environment_scope": "production",
+  "description": null
+}"#;
+
+/// Instance-level variables have no environment scope.
+const INSTANCE_VARIABLE: &str = r#"{
+  "key": "TEST_VARIABLE_1",
+  "variable_type": "env_var",
+  "value": "TEST_1",
+  "protected": false,
+  "masked": false,
+  "raw": false,
+  "description": null
+}"#;
+
+#[test]
+fn create_group_variable() {
+  let stub = Stub::start();
+  stub.on("POST", "/groups/1/variables", Response::new(201, VARIABLE));
+  let variable = stub
+    .run(&[
+      "groups",
+      "1",
+      "variables",
+      "create",
+      "--key",
+      "TEST_VARIABLE_1",
+      "--value",
+      "TEST_1",
+      "--masked",
+      "--environment-scope",
+      "production",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["key"], "TEST_VARIABLE_1");
+  assert_eq!(payload["masked"], true);
+  assert_eq!(payload["environment_scope"], "production");
+  assert_eq!(variable["environment_scope"], "production");
+}
+
+#[test]
+fn update_group_variable() {
+  let stub = Stub::start();
+  stub.on(
+    "PUT",
+    "/groups/1/variables/TEST_VARIABLE_1",
+    Response::ok(VARIABLE),
+  );
+  stub
+    .run(&[
+      "groups",
+      "1",
+      "variables",
+      "TEST_VARIABLE_1",
+      "update",
+      "--value",
+      "TEST_1",
+      "--protected",
+      "-f",
+      "json",
+    ])
+    .json();
+
+  let payload = stub.request().json();
+  assert_eq!(payload["value"], "TEST_1");
+  assert_eq!(payload["protected"], true);
+}
+
+#[test]
+fn list_instance_variables() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/admin/ci/variables",
+    Response::ok(format!("[{INSTANCE_VARIABLE}]")),
+  );
+  let variables = stub.run(&["admin", "ci", "variables", "-f", "json"]).json();
+
+  assert_eq!(variables[0]["key"], "TEST_VARIABLE_1");
+  assert_eq!(variables[0]["environment_scope"], "");
+}
+
+#[test]
+fn delete_instance_variable() {
+  let stub = Stub::start();
+  stub.on(
+    "DELETE",
+    "/admin/ci/variables/TEST_VARIABLE_1",
+    Response::new(204, ""),
+  );
+  stub
+    .run(&["admin", "ci", "variables", "TEST_VARIABLE_1", "delete"])
+    .ok();
+
+  assert_eq!(stub.request().method, "DELETE");
+}
+
+#[test]
+fn export_variables() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/3/variables",
+    Response::ok(format!(
+      r#"[{VARIABLE}, {{"key": "PLAIN", "value": "a \"quoted\" value", "protected": false, "masked": false, "raw": false, "environment_scope": "*"}}]"#
+    )),
+  );
+  let output = stub.run(&["projects", "3", "variables", "export"]).ok();
+
+  assert_eq!(
+    stub.request().query_param("per_page").as_deref(),
+    Some("100")
+  );
+  assert_eq!(
+    output,
+    r#"PLAIN="a \"quoted\" value"
+# gitlab-cli: environment_scope="production" masked
+TEST_VARIABLE_1="TEST_1"
+"#
+  );
+}
+
+#[test]
+fn export_variables_to_a_yaml_file() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/3/variables",
+    Response::ok(format!("[{VARIABLE}]")),
+  );
+  stub
+    .run(&[
+      "projects",
+      "3",
+      "variables",
+      "export",
+      "--file",
+      "variables.yml",
+    ])
+    .ok();
+
+  let content = std::fs::read_to_string(stub.home().join("variables.yml")).unwrap();
+  let variables: serde_json::Value = serde_yaml::from_str(&content).unwrap();
+  assert_eq!(variables[0]["key"], "TEST_VARIABLE_1");
+  assert_eq!(variables[0]["environment_scope"], "production");
+  assert_eq!(variables[0]["masked"], true);
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata = std::fs::metadata(stub.home().join("variables.yml")).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+  }
+}
+
+#[test]
+fn import_variables_dry_run() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/3/variables",
+    Response::ok(format!("[{VARIABLE}]")),
+  );
+  std::fs::write(
+    stub.home().join("variables.env"),
+    "# gitlab-cli: environment_scope=\"production\" masked protected\nTEST_VARIABLE_1=TEST_1\nNEW='new value'\n",
+  )
+  .unwrap();
+  let output = stub
+    .run(&[
+      "projects",
+      "3",
+      "variables",
+      "import",
+      "--file",
+      "variables.env",
+    ])
+    .ok();
+
+  assert_eq!(stub.request().method, "GET");
+  assert!(output.contains("+ NEW (*)\n"), "{output}");
+  assert!(
+    output.contains("~ TEST_VARIABLE_1 (production): protected\n"),
+    "{output}"
+  );
+  assert!(output.contains("Dry run"), "{output}");
+}
+
+#[test]
+fn import_variables() {
+  let stub = Stub::start();
+  stub.on(
+    "GET",
+    "/projects/3/variables",
+    Response::ok(format!(
+      r#"[{VARIABLE}, {{"key": "OLD", "value": "old", "protected": false, "masked": false, "raw": false, "environment_scope": "*"}}]"#
+    )),
+  );
+  stub.on(
+    "POST",
+    "/projects/3/variables",
+    Response::new(201, VARIABLE),
+  );
+  stub.on(
+    "PUT",
+    "/projects/3/variables/TEST_VARIABLE_1",
+    Response::ok(VARIABLE),
+  );
+  stub.on(
+    "DELETE",
+    "/projects/3/variables/OLD",
+    Response::new(204, ""),
+  );
+  std::fs::write(
+    stub.home().join("variables.json"),
+    r#"[
+      {"key": "TEST_VARIABLE_1", "value": "TEST_2", "environment_scope": "production", "masked": true},
+      {"key": "NEW", "value": "new", "variable_type": "file"}
+    ]"#,
+  )
+  .unwrap();
+  let output = stub
+    .run(&[
+      "projects",
+      "3",
+      "variables",
+      "import",
+      "--file",
+      "variables.json",
+      "--prune",
+      "--yes",
+    ])
+    .ok();
+
+  let requests = stub.requests();
+  let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+  assert_eq!(methods, ["GET", "POST", "PUT", "DELETE"]);
+  assert_eq!(requests[1].json()["key"], "NEW");
+  assert_eq!(requests[1].json()["variable_type"], "file");
+  assert_eq!(requests[2].json()["value"], "TEST_2");
+  assert_eq!(
+    requests[2]
+      .query_param("filter[environment_scope]")
+      .as_deref(),
+    Some("production")
+  );
+  assert_eq!(
+    requests[3]
+      .query_param("filter[environment_scope]")
+      .as_deref(),
+    Some("*")
+  );
+  assert!(
+    output.contains("1 created, 1 updated, 1 deleted."),
+    "{output}"
+  );
+}